@@ -1,4 +1,6 @@
-use re_space_view_waveform::types::archetypes::WaveformPoint;
+use re_space_view_waveform::types::archetypes::{
+    send_scalar_column, WaveformPoint, WaveformSample,
+};
 use re_types::datatypes::{AnnotationInfo, ClassDescription, ClassDescriptionMapElem, Rgba32};
 use re_viewer::external::{re_log, re_memory};
 
@@ -93,10 +95,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             let d_init = if d_init { 1 } else { 0 };
             let d_normal = if d_normal { 1 } else { 0 };
 
-            rec.log_static(d, &WaveformPoint::new_discrete_state_init(d_init))
-                .unwrap();
-            rec.log_static(d, &WaveformPoint::new_discrete_state_normal(d_normal))
-                .unwrap();
+            rec.log_static(
+                d,
+                &WaveformSample::new()
+                    .with_init(d_init)
+                    .with_normal(d_normal),
+            )
+            .unwrap();
         }
 
         let e_paths = ["E/e1", "E/e2"];
@@ -186,6 +191,15 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
         }
+
+        //Log a captured 10kHz burst of 100k samples at 1MHz in bulk, on its own timeline
+        let capture_time = re_sdk::Timeline::new_temporal("capture_time");
+        let times_ns: Vec<i64> = (0..100_000).map(|i| i * 1_000).collect();
+        let values: Vec<f64> = times_ns
+            .iter()
+            .map(|&t| (2.0 * std::f64::consts::PI * 10_000.0 * t as f64 * 1e-9).sin())
+            .collect();
+        send_scalar_column(&rec, "F/burst", capture_time, &times_ns, &values).unwrap();
     });
 
     re_viewer::run_native_app(
@@ -0,0 +1,306 @@
+use std::{collections::HashMap, ops::Bound};
+
+use crate::{series_cache::SeriesKey, AnalogPoints, WaveformTime};
+
+/// Multi-resolution min/max summary of an analog series.
+///
+/// Level `k` holds the indices of the min and max of consecutive blocks of `2^(k+1)` samples, so
+/// the min and max of any sample range, and where they are, can be read from `O(log n)` blocks.
+#[derive(Clone, Debug, Default)]
+pub struct MinMaxPyramid {
+    times: Vec<WaveformTime>,
+    values: Vec<f64>,
+    levels: Vec<Vec<(usize, usize)>>,
+}
+
+impl MinMaxPyramid {
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    /// Appends a sample, which must be later than all samples already in the pyramid
+    pub fn push(&mut self, time: WaveformTime, value: f64) {
+        self.times.push(time);
+        self.values.push(value);
+
+        let len = self.values.len();
+        let index = len - 1;
+
+        // Level `k` exists once it holds a full block
+        let mut k = 0;
+        while (2 << k) <= len {
+            let block = index >> (k + 1);
+
+            if k == self.levels.len() {
+                // The first block covers every sample so far, merge it from the level below
+                let first_block = if k == 0 {
+                    self.merge((0, 0), (1, 1))
+                } else {
+                    self.merge(self.levels[k - 1][0], self.levels[k - 1][1])
+                };
+                self.levels.push(vec![first_block]);
+            } else if block == self.levels[k].len() {
+                self.levels[k].push((index, index));
+            } else {
+                let merged = self.merge(self.levels[k][block], (index, index));
+                self.levels[k][block] = merged;
+            }
+
+            k += 1;
+        }
+    }
+
+    /// Indices of the min and max of two ranges given by the indices of their own min and max,
+    /// the earlier one on ties
+    fn merge(
+        &self,
+        (min_a, max_a): (usize, usize),
+        (min_b, max_b): (usize, usize),
+    ) -> (usize, usize) {
+        let min = if self.values[min_b] < self.values[min_a] {
+            min_b
+        } else {
+            min_a
+        };
+        let max = if self.values[max_b] > self.values[max_a] {
+            max_b
+        } else {
+            max_a
+        };
+        (min, max)
+    }
+
+    /// Indices of the min and max of the samples with indices in `start..end`
+    fn min_max(&self, mut start: usize, end: usize) -> Option<(usize, usize)> {
+        let mut acc: Option<(usize, usize)> = None;
+
+        while start < end {
+            // Largest block aligned at `start` that fits in the range
+            let mut size_log2 = 0;
+            while size_log2 < self.levels.len()
+                && start % (2 << size_log2) == 0
+                && start + (2 << size_log2) <= end
+            {
+                size_log2 += 1;
+            }
+
+            let block = if size_log2 == 0 {
+                start += 1;
+                (start - 1, start - 1)
+            } else {
+                let k = size_log2 - 1;
+                let block = self.levels[k][start >> (k + 1)];
+                start += 2 << k;
+                block
+            };
+
+            acc = Some(acc.map_or(block, |acc| self.merge(acc, block)));
+        }
+
+        acc
+    }
+
+    /// Points to plot for the time range `t_min..=t_max` drawn across `columns` pixel columns.
+    ///
+    /// Each column with more than two samples is reduced to its min and max, at the times they
+    /// occur and in that order, so peaks, glitches and the slope of edges stay visible. The
+    /// samples just outside the range are kept to connect the line to the plot edges.
+    pub fn decimate(
+        &self,
+        t_min: WaveformTime,
+        t_max: WaveformTime,
+        columns: usize,
+    ) -> Vec<(WaveformTime, f64)> {
+        let start = self.times.partition_point(|&t| t < t_min).saturating_sub(1);
+        let end = (self.times.partition_point(|&t| t <= t_max) + 1).min(self.times.len());
+
+        if start >= end {
+            return vec![];
+        }
+
+        let raw = |range: std::ops::Range<usize>| range.map(|i| (self.times[i], self.values[i]));
+
+        if end - start <= 2 * columns.max(1) {
+            return raw(start..end).collect();
+        }
+
+        let column_width = ((t_max - t_min) as f64 / columns as f64).max(1.0);
+        let mut points = Vec::with_capacity(2 * columns + 2);
+
+        // Sample before the range
+        points.extend(raw(start..(start + 1)));
+
+        let mut a = start + 1;
+        let last = end - 1;
+        for column in 1..=columns {
+            let column_end = t_min + (column as f64 * column_width) as WaveformTime;
+            let b = a + self.times[a..last].partition_point(|&t| t < column_end);
+
+            match b - a {
+                0 => {}
+                1 | 2 => points.extend(raw(a..b)),
+                _ => {
+                    if let Some((min, max)) = self.min_max(a, b) {
+                        let (first, second) = (min.min(max), min.max(max));
+                        points.extend(raw(first..(first + 1)));
+                        if second != first {
+                            points.extend(raw(second..(second + 1)));
+                        }
+                    }
+                }
+            }
+
+            a = b;
+        }
+
+        // Remaining samples in the range, and the sample after it
+        points.extend(raw(a..end));
+
+        points
+    }
+}
+
+/// Cache of [`MinMaxPyramid`]s per analog series and space view, kept across frames
+#[derive(Default)]
+pub struct AnalogPyramidCache {
+    pyramids: HashMap<(SeriesKey, Option<usize>), CachedPyramid>,
+}
+
+struct CachedPyramid {
+    pyramid: MinMaxPyramid,
+    /// [`AnalogPoints::generation`] the pyramid was built from
+    generation: u64,
+    used_this_frame: bool,
+}
+
+impl AnalogPyramidCache {
+    /// Pyramid of `points`, built once and extended as later samples are appended. It is rebuilt
+    /// once the points change in any other way
+    pub fn pyramid(
+        &mut self,
+        key: SeriesKey,
+        instance: Option<usize>,
+        points: &AnalogPoints,
+    ) -> &MinMaxPyramid {
+        let cached = self
+            .pyramids
+            .entry((key, instance))
+            .or_insert_with(|| CachedPyramid {
+                pyramid: Default::default(),
+                generation: points.generation,
+                used_this_frame: true,
+            });
+        cached.used_this_frame = true;
+
+        let pyramid = &mut cached.pyramid;
+
+        if cached.generation != points.generation {
+            cached.generation = points.generation;
+            *pyramid = MinMaxPyramid::default();
+        }
+
+        let appended = match pyramid.times.last() {
+            Some(last_time) => points
+                .points
+                .range((Bound::Excluded(*last_time), Bound::Unbounded)),
+            None => points.points.range(..),
+        };
+        for (time, point) in appended {
+            pyramid.push(*time, point.value);
+        }
+
+        pyramid
+    }
+}
+
+impl re_viewer_context::Cache for AnalogPyramidCache {
+    fn begin_frame(&mut self) {
+        // Drop pyramids of series that are no longer drawn
+        self.pyramids
+            .retain(|_, cached| std::mem::replace(&mut cached.used_this_frame, false));
+    }
+
+    fn purge_memory(&mut self) {
+        self.pyramids.clear();
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pyramid(values: &[f64]) -> MinMaxPyramid {
+        let mut pyramid = MinMaxPyramid::default();
+        for (i, value) in values.iter().enumerate() {
+            pyramid.push(i as WaveformTime * 10, *value);
+        }
+        pyramid
+    }
+
+    #[test]
+    fn min_max_matches_every_range() {
+        let values = [3.0, -1.0, 4.0, 1.0, -5.0, 9.0, 2.0, 6.0, -5.0, 3.0, 5.0];
+        let pyramid = pyramid(&values);
+
+        for start in 0..values.len() {
+            for end in (start + 1)..=values.len() {
+                let (min, max) = pyramid.min_max(start, end).unwrap();
+                let range = &values[start..end];
+                let expected_min = range.iter().copied().fold(f64::INFINITY, f64::min);
+                let expected_max = range.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                assert_eq!(values[min], expected_min, "min of {start}..{end}");
+                assert_eq!(values[max], expected_max, "max of {start}..{end}");
+                assert!((start..end).contains(&min) && (start..end).contains(&max));
+            }
+        }
+
+        // Ties resolve to the earlier sample
+        assert_eq!(pyramid.min_max(0, values.len()).unwrap().0, 4);
+    }
+
+    #[test]
+    fn empty_range_has_no_min_max() {
+        assert_eq!(pyramid(&[1.0, 2.0]).min_max(1, 1), None);
+        assert!(MinMaxPyramid::default().decimate(0, 100, 10).is_empty());
+    }
+
+    #[test]
+    fn few_samples_are_kept_as_they_are() {
+        let pyramid = pyramid(&[0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(
+            pyramid.decimate(0, 30, 10),
+            vec![(0, 0.0), (10, 1.0), (20, 2.0), (30, 3.0)]
+        );
+    }
+
+    #[test]
+    fn extremes_are_drawn_where_they_occur() {
+        // A falling edge with a spike, all within one column
+        let values = [5.0, 5.0, 9.0, 5.0, 4.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        let pyramid = pyramid(&values);
+
+        let points = pyramid.decimate(0, 90, 1);
+        assert_eq!(points.first(), Some(&(0, 5.0)));
+        assert_eq!(points.last(), Some(&(90, 0.0)));
+
+        // The spike comes before the minimum, at their own times
+        let inner = &points[1..points.len() - 1];
+        assert_eq!(inner, &[(20, 9.0), (50, 0.0)]);
+    }
+
+    #[test]
+    fn decimated_points_are_in_time_order() {
+        let values = (0..1000)
+            .map(|i| ((i * 37) % 101) as f64)
+            .collect::<Vec<_>>();
+        let pyramid = pyramid(&values);
+
+        let points = pyramid.decimate(1000, 8000, 20);
+        assert!(points.len() <= 2 * 20 + 2 + 2);
+        assert!(points.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+}
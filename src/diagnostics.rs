@@ -0,0 +1,85 @@
+use std::collections::BTreeSet;
+
+use re_types::components::ClassId;
+
+/// Reason a row, or part of it, isn't drawn as logged
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RowIssue {
+    /// The class isn't described by any `AnnotationContext` of the entity
+    UnknownClassId(ClassId),
+
+    /// The row holds fewer instances than the entity has sub-channels
+    MissingInstances { found: usize },
+}
+
+/// Warnings collected for one entity while loading its rows
+#[derive(Clone, Debug, Default)]
+pub struct EntityDiagnostics {
+    unknown_class_ids: BTreeSet<u16>,
+    unknown_class_rows: usize,
+    missing_instance_rows: usize,
+    channel_count: usize,
+    pending: bool,
+}
+
+impl EntityDiagnostics {
+    pub fn new<'a>(
+        issues: impl IntoIterator<Item = &'a RowIssue>,
+        channel_count: usize,
+        pending: bool,
+    ) -> Self {
+        let mut diagnostics = Self {
+            channel_count,
+            pending,
+            ..Default::default()
+        };
+
+        for issue in issues {
+            match issue {
+                RowIssue::UnknownClassId(class_id) => {
+                    diagnostics.unknown_class_ids.insert(class_id.0 .0);
+                    diagnostics.unknown_class_rows += 1;
+                }
+                RowIssue::MissingInstances { .. } => {
+                    diagnostics.missing_instance_rows += 1;
+                }
+            }
+        }
+
+        diagnostics
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.unknown_class_rows == 0 && self.missing_instance_rows == 0 && !self.pending
+    }
+
+    /// Human readable warnings, one per kind of issue
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = vec![];
+
+        if self.unknown_class_rows > 0 {
+            warnings.push(format!(
+                "{} values dropped: class ids {} are not in any AnnotationContext",
+                self.unknown_class_rows,
+                self.unknown_class_ids
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        if self.missing_instance_rows > 0 {
+            warnings.push(format!(
+                "{} rows hold fewer instances than the {} sub-channels",
+                self.missing_instance_rows, self.channel_count
+            ));
+        }
+
+        if self.pending {
+            warnings.push("Some data is still loading".to_owned());
+        }
+
+        warnings
+    }
+}
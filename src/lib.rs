@@ -1,31 +1,76 @@
 pub mod types;
 use std::collections::BTreeMap;
 
-use re_sdk::{EntityPath, EntityPathPart};
+use re_sdk::EntityPath;
+use re_viewer_context::SpaceViewId;
 
 mod annotation_context;
+mod decimation;
+mod diagnostics;
+mod measurements;
+mod search;
+mod series_cache;
 mod space_view_class;
+mod units;
+mod view_state;
 mod visualizer_system;
 
 pub use space_view_class::WaveformSpaceView;
 
 type WaveformTime = i64;
-type WaveformDomain = EntityPathPart;
+/// Name of a group of series drawn in the same plot
+type WaveformDomain = String;
+
+/// Id of the egui temp data holding the time window the waveform plots of a space view showed
+/// last frame, which the visualizer limits its queries to
+fn visible_window_id(space_view_id: SpaceViewId, timeline_name: &str) -> egui::Id {
+    egui::Id::new(("visible_window", space_view_id, timeline_name))
+}
+
+/// Id of the egui temp data holding the [`search::SearchRequest`] a space view posted for its
+/// visualizer to run next frame
+fn search_request_id(space_view_id: SpaceViewId, timeline_name: &str) -> egui::Id {
+    egui::Id::new(("search_request", space_view_id, timeline_name))
+}
 
 #[derive(Clone, Debug)]
 struct WaveformSeries {
     pub entity_path: EntityPath,
+    /// Instance of the entity's rows this series holds, if they have several
+    pub instance: Option<usize>,
     pub min_time: WaveformTime,
     pub max_time: WaveformTime,
     pub analog_points: AnalogPoints,
     pub discrete_points: DiscretePoints,
     pub color: egui::Color32,
+    /// Physical unit of the analog points
+    pub unit: Option<String>,
+    /// How the analog points are joined
+    pub interpolation: types::components::Interpolation,
+    /// Time range within which the series holds every row, so cursor lookups can be resolved
+    pub queried_range: (WaveformTime, WaveformTime),
+    /// Changes whenever the rows of the series change, unique across all series, so that
+    /// measurements made from them know to be made again
+    pub generation: u64,
 }
 
 impl WaveformSeries {
     pub fn len_series(&self) -> usize {
         self.analog_points.len() + self.discrete_points.len()
     }
+
+    /// Display name, with the instance index for entities split into several channels
+    pub fn name(&self) -> String {
+        match self.instance {
+            Some(instance) => format!("{}[{instance}]", self.entity_path),
+            None => self.entity_path.to_string(),
+        }
+    }
+
+    /// Whether every row around `time` has been queried
+    pub fn covers(&self, time: WaveformTime) -> bool {
+        self.queried_range.0 <= time && time <= self.queried_range.1
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -46,6 +91,15 @@ impl WaveformEvents {
         self.event_markers.iter()
     }
 
+    /// Events from `start` to `end` inclusive
+    pub fn range(
+        &self,
+        start: WaveformTime,
+        end: WaveformTime,
+    ) -> impl Iterator<Item = (&WaveformTime, &Vec<EventMarker>)> {
+        self.event_markers.range(start..=end)
+    }
+
     pub fn push(&mut self, time: WaveformTime, event_marker: EventMarker) {
         self.event_markers
             .entry(time)
@@ -59,9 +113,27 @@ struct AnalogPoints {
     pub points: BTreeMap<WaveformTime, AnalogPoint>,
     /// Optional min and max values for the analog points when points is non-empty
     pub y_range: Option<(f64, f64)>,
+    /// Changes whenever points change other than by appending later ones, unique across all
+    /// series, so that summaries built from the points know to rebuild
+    pub generation: u64,
+}
+
+/// Source of [`AnalogPoints::generation`]s and [`WaveformSeries::generation`]s
+static NEXT_GENERATION: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+fn next_generation() -> u64 {
+    NEXT_GENERATION.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
 }
 
 impl AnalogPoints {
+    pub fn new() -> Self {
+        Self {
+            points: BTreeMap::new(),
+            y_range: None,
+            generation: next_generation(),
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.points.len()
     }
@@ -73,8 +145,87 @@ impl AnalogPoints {
         self.points.iter()
     }
 
+    /// Value at `time`, and whether it is in between samples rather than one within
+    /// `tolerance`. There is none before the first or after the last sample
+    pub fn value_at(
+        &self,
+        time: WaveformTime,
+        tolerance: WaveformTime,
+        interpolation: types::components::Interpolation,
+    ) -> Option<(f64, bool)> {
+        use std::ops::Bound;
+        use types::components::Interpolation;
+
+        if let Some((_, point)) = self
+            .points
+            .range(time.saturating_sub(tolerance)..=time.saturating_add(tolerance))
+            .next()
+        {
+            return Some((point.value, false));
+        }
+
+        let (t1, p1) = self
+            .points
+            .range((Bound::Unbounded, Bound::Excluded(&time)))
+            .next_back()?;
+        let (t2, p2) = self
+            .points
+            .range((Bound::Excluded(&time), Bound::Unbounded))
+            .next()?;
+
+        let value = match interpolation {
+            Interpolation::Linear => {
+                let slope = (p2.value - p1.value) / (*t2 - *t1) as f64;
+                p1.value + slope * (time - *t1) as f64
+            }
+            Interpolation::PreviousHold => p1.value,
+            Interpolation::Nearest if time - *t1 <= *t2 - time => p1.value,
+            Interpolation::Nearest => p2.value,
+            Interpolation::Points => return None,
+        };
+
+        Some((value, true))
+    }
+
     pub fn push(&mut self, time: WaveformTime, value: f64) {
-        self.points.insert(time, AnalogPoint { value });
+        if self
+            .points
+            .last_key_value()
+            .is_some_and(|(last, _)| *last >= time)
+        {
+            self.generation = next_generation();
+        }
+        let replaced = self.points.insert(time, AnalogPoint { value });
+
+        match (self.y_range, replaced) {
+            // The replaced value may have been the min or max
+            (Some((y_min, y_max)), Some(replaced))
+                if replaced.value <= y_min || replaced.value >= y_max =>
+            {
+                self.update_y_range();
+            }
+            (Some((y_min, y_max)), _) => self.y_range = Some((y_min.min(value), y_max.max(value))),
+            (None, _) => self.y_range = Some((value, value)),
+        }
+    }
+
+    /// Drops all points at or after `time`
+    pub fn truncate_from(&mut self, time: WaveformTime) {
+        let removed = self.points.split_off(&time);
+        if removed.is_empty() {
+            return;
+        }
+
+        self.generation = next_generation();
+        self.update_y_range();
+    }
+
+    fn update_y_range(&mut self) {
+        self.y_range = self.points.values().fold(None, |range, p| {
+            Some(range.map_or((p.value, p.value), |(min, max): (f64, f64)| {
+                (min.min(p.value), max.max(p.value))
+            }))
+        });
     }
 }
 
@@ -124,6 +275,13 @@ struct AnalogPoint {
 enum DiscreteTransitionKind {
     Line,
     Box,
+    /// Multi-bit bus value, labelled in the radix chosen in the view
+    Bus {
+        value: u64,
+        width: u8,
+    },
+    /// Four-state logic bit, drawn as a digital waveform
+    Logic(types::components::LogicState),
 }
 
 #[derive(Clone, Debug)]
@@ -133,9 +291,21 @@ struct DiscreteTransition {
     pub kind: DiscreteTransitionKind,
 }
 
+impl DiscreteTransition {
+    /// Label to show for the state, bus values are formatted in `radix`
+    pub fn display_label(&self, radix: view_state::Radix) -> Option<String> {
+        match self.kind {
+            DiscreteTransitionKind::Bus { value, width } => Some(radix.format(value, width)),
+            _ => self.label.clone(),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 struct EventMarker {
     pub entity_path: EntityPath,
     pub label: Option<String>,
     pub color: egui::Color32,
+    /// Duration of a span, instant events have none
+    pub duration: Option<WaveformTime>,
 }
@@ -0,0 +1,898 @@
+use std::{
+    cell::OnceCell,
+    collections::{BTreeMap, HashMap},
+    ops::Bound,
+};
+
+use itertools::Itertools;
+use re_log_types::TimeType;
+use re_sdk::EntityPath;
+
+use crate::{
+    series_cache::SeriesKey,
+    types::components::{Interpolation, LogicState},
+    view_state::Radix,
+    AnalogPoints, DiscretePoints, DiscreteTransition, DiscreteTransitionKind, WaveformEvents,
+    WaveformSeries, WaveformTime,
+};
+
+/// Hysteresis around the middle of the swing of an analog signal, as a fraction of the swing,
+/// within which threshold crossings don't count as edges
+const ANALOG_HYSTERESIS_PC: f64 = 0.1;
+
+/// Seconds per time unit of a timeline, sequence timelines have no time unit
+pub fn seconds_per_time_unit(time_type: TimeType) -> Option<f64> {
+    match time_type {
+        TimeType::Time => Some(1e-9),
+        TimeType::Sequence => None,
+    }
+}
+
+/// Statistics of an analog series in between two times
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnalogMeasurements {
+    pub min: f64,
+    pub max: f64,
+    /// Mean over time, or over the samples when they are drawn as points
+    pub mean: f64,
+    pub rms: f64,
+    /// Value at the end of the window minus the one at its start
+    pub delta_y: f64,
+    /// `delta_y` per time unit of the timeline
+    pub slope: Option<f64>,
+    /// Integral over the window in value times time unit, none for samples drawn as points
+    pub area: Option<f64>,
+}
+
+impl AnalogMeasurements {
+    /// Measures `points` in between `start` and `end`, joined by `interpolation` like they are
+    /// drawn. There are none without a value in the window
+    pub fn measure(
+        points: &AnalogPoints,
+        interpolation: Interpolation,
+        start: WaveformTime,
+        end: WaveformTime,
+    ) -> Option<Self> {
+        let (start, end) = (start.min(end), start.max(end));
+
+        // Values at the edges of the window, then every sample in between
+        let edge = |time| {
+            points
+                .value_at(time, 0, interpolation)
+                .map(|(v, _)| (time, v))
+        };
+        let mut samples = Vec::from_iter(edge(start));
+        if start < end {
+            samples.extend(
+                points
+                    .points
+                    .range((Bound::Excluded(start), Bound::Excluded(end)))
+                    .map(|(t, point)| (*t, point.value)),
+            );
+            samples.extend(edge(end));
+        }
+
+        let (&(first_time, first), &(last_time, last)) = (samples.first()?, samples.last()?);
+
+        let (min, max) = min_max(samples.iter().copied());
+
+        let duration = (last_time - first_time) as f64;
+        let (mean, rms, area) = if interpolation != Interpolation::Points && duration > 0.0 {
+            let (area, square_area) = samples.iter().tuple_windows().fold(
+                (0.0, 0.0),
+                |(area, square_area), (&(t1, a), &(t2, b))| {
+                    let (segment, square_segment) =
+                        segment_integrals(interpolation, (t2 - t1) as f64, a, b);
+                    (area + segment, square_area + square_segment)
+                },
+            );
+            (area / duration, (square_area / duration).sqrt(), Some(area))
+        } else {
+            let n = samples.len() as f64;
+            let mean = samples.iter().map(|(_, v)| v).sum::<f64>() / n;
+            let rms = (samples.iter().map(|(_, v)| v * v).sum::<f64>() / n).sqrt();
+            (mean, rms, None)
+        };
+
+        Some(Self {
+            min,
+            max,
+            mean,
+            rms,
+            delta_y: last - first,
+            slope: (duration > 0.0).then(|| (last - first) / duration),
+            area,
+        })
+    }
+
+    pub fn peak_to_peak(&self) -> f64 {
+        self.max - self.min
+    }
+}
+
+/// Integrals of the value and of its square over a segment `dt` long from `a` to `b`
+fn segment_integrals(interpolation: Interpolation, dt: f64, a: f64, b: f64) -> (f64, f64) {
+    match interpolation {
+        Interpolation::Linear => (dt * (a + b) / 2.0, dt * (a * a + a * b + b * b) / 3.0),
+        Interpolation::PreviousHold => (dt * a, dt * a * a),
+        // Half the segment at each value, points are not integrated
+        Interpolation::Nearest | Interpolation::Points => {
+            (dt * (a + b) / 2.0, dt * (a * a + b * b) / 2.0)
+        }
+    }
+}
+
+/// Discrete transitions and events of an entity in between two times, counted per label
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DiscreteCounts {
+    pub transitions: BTreeMap<String, usize>,
+    pub events: BTreeMap<String, usize>,
+}
+
+impl DiscreteCounts {
+    /// Counts the transitions of `discrete_points`, and the events of `entity_path`, from `start`
+    /// to `end` inclusive. Bus values are labelled in `radix`
+    pub fn count(
+        discrete_points: &DiscretePoints,
+        events: &WaveformEvents,
+        entity_path: &EntityPath,
+        radix: Radix,
+        start: WaveformTime,
+        end: WaveformTime,
+    ) -> Self {
+        let (start, end) = (start.min(end), start.max(end));
+
+        let transitions = discrete_points
+            .transitions
+            .range(start..=end)
+            .map(|(_, transition)| label_or_unnamed(transition.display_label(radix)))
+            .counts()
+            .into_iter()
+            .collect();
+
+        let events = events
+            .range(start, end)
+            .flat_map(|(_, markers)| markers)
+            .filter(|marker| &marker.entity_path == entity_path)
+            .map(|marker| label_or_unnamed(marker.label.clone()))
+            .counts()
+            .into_iter()
+            .collect();
+
+        Self {
+            transitions,
+            events,
+        }
+    }
+
+    pub fn total_transitions(&self) -> usize {
+        self.transitions.values().sum()
+    }
+
+    pub fn total_events(&self) -> usize {
+        self.events.values().sum()
+    }
+}
+
+fn label_or_unnamed(label: Option<String>) -> String {
+    label.unwrap_or_else(|| "(unnamed)".to_owned())
+}
+
+/// Period, frequency and duty cycle of a periodic signal
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PeriodicMeasurements {
+    /// Mean time between rising edges, in time units of the timeline
+    pub period: f64,
+    /// Mean time from a rising edge to the following falling one
+    pub pulse_width: Option<f64>,
+}
+
+impl PeriodicMeasurements {
+    /// Cycles per time unit of the timeline
+    pub fn frequency(&self) -> f64 {
+        1.0 / self.period
+    }
+
+    /// Fraction of the period spent high
+    pub fn duty_cycle(&self) -> Option<f64> {
+        self.pulse_width.map(|width| width / self.period)
+    }
+
+    /// Measures the transitions from `start` to `end`. Logic bits are high while `1`, other
+    /// states while they are the state in effect at `start`
+    pub fn of_discrete(
+        points: &DiscretePoints,
+        start: WaveformTime,
+        end: WaveformTime,
+    ) -> Option<Self> {
+        let (start, end) = (start.min(end), start.max(end));
+
+        let mut transitions = points
+            .transitions
+            .range(..start)
+            .next_back()
+            .into_iter()
+            .chain(points.transitions.range(start..=end))
+            .peekable();
+
+        let (_, reference) = *transitions.peek()?;
+        let is_high = |transition: &DiscreteTransition| match (&reference.kind, &transition.kind) {
+            (_, DiscreteTransitionKind::Logic(state)) => *state == LogicState::High,
+            (
+                DiscreteTransitionKind::Bus { value: a, .. },
+                DiscreteTransitionKind::Bus { value: b, .. },
+            ) => a == b,
+            _ => transition.label == reference.label,
+        };
+
+        Self::from_levels(transitions.map(|(t, transition)| (*t as f64, is_high(transition))))
+    }
+
+    /// Measures the crossings of the middle of the swing of `points` from `start` to `end`.
+    /// The signal goes high once above the hysteresis band, and low once below it, at the time
+    /// it last crossed the middle
+    pub fn of_analog(
+        points: &AnalogPoints,
+        start: WaveformTime,
+        end: WaveformTime,
+    ) -> Option<Self> {
+        Self::from_levels(analog_levels(analog_samples(points, start, end))?)
+    }
+
+    /// Measures the analog points of `series` from `start` to `end`, or else its transitions
+    pub fn of_series(
+        series: &WaveformSeries,
+        start: WaveformTime,
+        end: WaveformTime,
+    ) -> Option<Self> {
+        Self::of_analog(&series.analog_points, start, end)
+            .or_else(|| Self::of_discrete(&series.discrete_points, start, end))
+    }
+
+    /// Measures a signal from its level over time, the first level is the one in effect before
+    /// any edge
+    fn from_levels(levels: impl IntoIterator<Item = (f64, bool)>) -> Option<Self> {
+        let mut rising_edges = vec![];
+        let mut pulse_widths = vec![];
+        let mut last_rising = None;
+        for (time, high) in edges(levels) {
+            if high {
+                rising_edges.push(time);
+                last_rising = Some(time);
+            } else if let Some(rising) = last_rising.take() {
+                pulse_widths.push(time - rising);
+            }
+        }
+
+        let (first, last) = (rising_edges.first()?, rising_edges.last()?);
+        let cycles = rising_edges.len() - 1;
+        if cycles == 0 {
+            return None;
+        }
+
+        Some(Self {
+            period: (last - first) / cycles as f64,
+            pulse_width: (!pulse_widths.is_empty())
+                .then(|| pulse_widths.iter().sum::<f64>() / pulse_widths.len() as f64),
+        })
+    }
+}
+
+/// Measurements of an analog edge, as fractions of its swing and in time units of the timeline
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EdgeMeasurements {
+    pub rising: bool,
+    /// Time from 10 % to 90 % of the swing
+    pub transition_time: Option<f64>,
+    /// Largest excursion past the level the edge settles at
+    pub overshoot: f64,
+    /// Largest excursion past the level the edge starts from, before it
+    pub undershoot: f64,
+    /// Time from the middle of the swing until the signal stays within [`SETTLING_BAND_PC`] of
+    /// the level it settles at, there is none if it doesn't before the next edge
+    pub settling_time: Option<f64>,
+}
+
+/// Band around the final level of an edge, as a fraction of its swing, within which it is settled
+pub const SETTLING_BAND_PC: f64 = 0.02;
+
+/// Edges of an analog series in between two times, and the levels they swing between
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnalogEdges {
+    low: f64,
+    high: f64,
+    /// Times and directions of the edges, in time order
+    edges: Vec<(f64, bool)>,
+}
+
+impl AnalogEdges {
+    /// Finds the edges of `points` from `start` to `end`. The levels the edges swing between are
+    /// the most common values in the lower and upper half of the range
+    pub fn find(points: &AnalogPoints, start: WaveformTime, end: WaveformTime) -> Option<Self> {
+        let samples = analog_samples(points, start, end);
+        let (low, high) = state_levels(samples.clone())?;
+        let edges = edges(analog_levels(samples)?).collect_vec();
+
+        Some(Self { low, high, edges })
+    }
+
+    /// Index of the edge nearest to `cursor`
+    pub fn nearest(&self, cursor: WaveformTime) -> Option<usize> {
+        let cursor = cursor as f64;
+        let after = self.edges.partition_point(|(t, _)| *t < cursor);
+        after
+            .checked_sub(1)
+            .into_iter()
+            .chain((after < self.edges.len()).then_some(after))
+            .min_by(|a, b| {
+                let distance = |i: &usize| (self.edges[*i].0 - cursor).abs();
+                distance(a).total_cmp(&distance(b))
+            })
+    }
+}
+
+impl EdgeMeasurements {
+    /// Measures edge `i` of `edges`, found in `points` from `start` to `end`, with values in
+    /// between samples found by `interpolation` like in the side panel
+    fn measure(
+        points: &AnalogPoints,
+        interpolation: Interpolation,
+        start: WaveformTime,
+        end: WaveformTime,
+        edges: &AnalogEdges,
+        i: usize,
+    ) -> Option<Self> {
+        let (low, high, edges) = (edges.low, edges.high, &edges.edges);
+        let (time, rising) = *edges.get(i)?;
+
+        // Samples from halfway to the previous edge to halfway to the next one, 0 at the level
+        // the edge starts from and 1 at the one it settles at
+        let from = i
+            .checked_sub(1)
+            .map_or(f64::NEG_INFINITY, |i| (edges[i].0 + time) / 2.0);
+        let to = edges
+            .get(i + 1)
+            .map_or(f64::INFINITY, |(t, _)| (time + t) / 2.0);
+        let (initial, settled) = if rising { (low, high) } else { (high, low) };
+        let (start, end) = (start.min(end), start.max(end));
+        let around = analog_samples(
+            points,
+            (from.floor() as WaveformTime).max(start),
+            (to.ceil() as WaveformTime).min(end),
+        )
+        .filter(|(t, _)| from < *t && *t < to)
+        .map(|(t, v)| (t, (v - initial) / (settled - initial)))
+        .collect_vec();
+
+        // Segment crossing the middle of the swing closest to the edge
+        let crossing = |level: f64, (t1, a): (f64, f64), (t2, b): (f64, f64)| {
+            (a < level && level <= b)
+                .then(|| segment_crossing(interpolation, (t1, a), (t2, b), level))
+        };
+        let segments = around.iter().copied().tuple_windows().collect_vec();
+        let (k, middle) = segments
+            .iter()
+            .enumerate()
+            .filter_map(|(k, &(a, b))| Some((k, crossing(0.5, a, b)?)))
+            .min_by(|(_, a), (_, b)| (a - time).abs().total_cmp(&(b - time).abs()))?;
+
+        let low_crossing = segments[..=k]
+            .iter()
+            .rev()
+            .find_map(|&(a, b)| crossing(0.1, a, b));
+        let high_crossing = segments[k..].iter().find_map(|&(a, b)| crossing(0.9, a, b));
+
+        let (before, after) = around.split_at(k + 1);
+        let overshoot = after.iter().map(|(_, x)| x - 1.0).fold(0.0, f64::max);
+        let undershoot = before.iter().map(|(_, x)| -x).fold(0.0, f64::max);
+
+        // Settled from the last segment coming into the band on, unless it ends outside of it
+        let out_of_band = |x: f64| (x - 1.0).abs() > SETTLING_BAND_PC;
+        let settled = around.last().is_some_and(|(_, x)| !out_of_band(*x));
+        let settling_time = segments[k..]
+            .iter()
+            .rev()
+            .find(|(a, _)| out_of_band(a.1))
+            .filter(|_| settled)
+            .map(|&((t1, a), (t2, b))| {
+                let level = 1.0 + SETTLING_BAND_PC.copysign(a - 1.0);
+                segment_crossing(interpolation, (t1, a), (t2, b), level) - middle
+            });
+
+        Some(Self {
+            rising,
+            transition_time: low_crossing
+                .zip(high_crossing)
+                .map(|(low, high)| high - low),
+            overshoot,
+            undershoot,
+            settling_time,
+        })
+    }
+}
+
+/// Cache of the frequency and edge measurements per series and space view, kept across frames
+#[derive(Default)]
+pub struct MeasurementCache {
+    series: HashMap<(SeriesKey, Option<usize>), CachedMeasurements>,
+}
+
+/// Measurements of a series, each made once it is first asked for
+struct CachedMeasurements {
+    /// [`WaveformSeries::generation`] and window the measurements are made for
+    generation: u64,
+    window: (WaveformTime, WaveformTime),
+    periodic: OnceCell<Option<PeriodicMeasurements>>,
+    edges: OnceCell<Option<AnalogEdges>>,
+    /// Edge last measured, with the interpolation it was measured with
+    edge: Option<((usize, Interpolation), Option<EdgeMeasurements>)>,
+    used_this_frame: bool,
+}
+
+impl CachedMeasurements {
+    fn new(generation: u64, window: (WaveformTime, WaveformTime)) -> Self {
+        Self {
+            generation,
+            window,
+            periodic: OnceCell::new(),
+            edges: OnceCell::new(),
+            edge: None,
+            used_this_frame: true,
+        }
+    }
+}
+
+impl MeasurementCache {
+    /// Measurements of `series` over `window`, dropped once the series or the window changes
+    fn measurements(
+        &mut self,
+        key: SeriesKey,
+        series: &WaveformSeries,
+        window: (WaveformTime, WaveformTime),
+    ) -> &mut CachedMeasurements {
+        let cached = self
+            .series
+            .entry((key, series.instance))
+            .or_insert_with(|| CachedMeasurements::new(series.generation, window));
+
+        if cached.generation != series.generation || cached.window != window {
+            *cached = CachedMeasurements::new(series.generation, window);
+        }
+
+        cached.used_this_frame = true;
+        cached
+    }
+
+    /// Periodic measurements of `series` from `start` to `end`
+    pub fn periodic(
+        &mut self,
+        key: SeriesKey,
+        series: &WaveformSeries,
+        start: WaveformTime,
+        end: WaveformTime,
+    ) -> Option<PeriodicMeasurements> {
+        *self
+            .measurements(key, series, (start, end))
+            .periodic
+            .get_or_init(|| PeriodicMeasurements::of_series(series, start, end))
+    }
+
+    /// Measurements of the analog edge of `series` nearest to `cursor` from `start` to `end`.
+    /// The edges are found once, an edge is measured again once another one is nearest
+    pub fn nearest_edge(
+        &mut self,
+        key: SeriesKey,
+        series: &WaveformSeries,
+        start: WaveformTime,
+        end: WaveformTime,
+        cursor: WaveformTime,
+    ) -> Option<EdgeMeasurements> {
+        let cached = self.measurements(key, series, (start, end));
+        let edges = cached
+            .edges
+            .get_or_init(|| AnalogEdges::find(&series.analog_points, start, end))
+            .as_ref()?;
+
+        let measured = (edges.nearest(cursor)?, series.interpolation);
+        match cached.edge {
+            Some((edge, measurements)) if edge == measured => measurements,
+            _ => {
+                let measurements = EdgeMeasurements::measure(
+                    &series.analog_points,
+                    series.interpolation,
+                    start,
+                    end,
+                    edges,
+                    measured.0,
+                );
+                cached.edge = Some((measured, measurements));
+                measurements
+            }
+        }
+    }
+}
+
+impl re_viewer_context::Cache for MeasurementCache {
+    fn begin_frame(&mut self) {
+        // Drop measurements of series that are no longer shown
+        self.series
+            .retain(|_, cached| std::mem::replace(&mut cached.used_this_frame, false));
+    }
+
+    fn purge_memory(&mut self) {
+        self.series.clear();
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+/// Time at which a segment from `(t1, a)` to `(t2, b)` reaches `level`, joined by `interpolation`
+pub fn segment_crossing(
+    interpolation: Interpolation,
+    (t1, a): (f64, f64),
+    (t2, b): (f64, f64),
+    level: f64,
+) -> f64 {
+    match interpolation {
+        Interpolation::Linear | Interpolation::Points => t1 + (level - a) / (b - a) * (t2 - t1),
+        Interpolation::PreviousHold => t2,
+        Interpolation::Nearest => (t1 + t2) / 2.0,
+    }
+}
+
+/// Samples of `points` from `start` to `end`, with times as floats. They are iterated over
+/// rather than collected, as the window may hold any number of them
+fn analog_samples(
+    points: &AnalogPoints,
+    start: WaveformTime,
+    end: WaveformTime,
+) -> impl Iterator<Item = (f64, f64)> + Clone + '_ {
+    let (start, end) = (start.min(end), start.max(end));
+    points
+        .points
+        .range(start..=end)
+        .map(|(t, p)| (*t as f64, p.value))
+}
+
+fn min_max<T>(samples: impl IntoIterator<Item = (T, f64)>) -> (f64, f64) {
+    samples
+        .into_iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), (_, v)| {
+            (min.min(v), max.max(v))
+        })
+}
+
+/// Level of an analog signal over time, against the middle of its swing. The signal goes high
+/// once above the hysteresis band, and low once below it, at the time it last crossed the middle.
+/// There is none for a flat signal
+fn analog_levels(
+    samples: impl Iterator<Item = (f64, f64)> + Clone,
+) -> Option<impl Iterator<Item = (f64, bool)>> {
+    let (min, max) = min_max(samples.clone());
+    if max <= min {
+        return None;
+    }
+
+    let middle = (min + max) / 2.0;
+    let hysteresis = (max - min) * ANALOG_HYSTERESIS_PC;
+
+    let mut crossing = None;
+    let levels = samples
+        .tuple_windows()
+        .filter_map(move |((t1, v1), (t2, v2))| {
+            if (v1 < middle) != (v2 < middle) {
+                crossing = Some(t1 + (middle - v1) / (v2 - v1) * (t2 - t1));
+            }
+
+            let high = if v2 > middle + hysteresis {
+                true
+            } else if v2 < middle - hysteresis {
+                false
+            } else {
+                return None;
+            };
+            Some((crossing.unwrap_or(t2), high))
+        });
+
+    Some(levels)
+}
+
+/// Times and directions of the changes of `levels`, the first level is the one in effect before
+/// any edge
+fn edges(levels: impl IntoIterator<Item = (f64, bool)>) -> impl Iterator<Item = (f64, bool)> {
+    levels.into_iter().dedup_by(|(_, a), (_, b)| a == b).skip(1)
+}
+
+/// Most common values in the lower and upper half of the range of `samples`, as the mean of the
+/// samples in the fullest histogram bin of each half
+fn state_levels(samples: impl Iterator<Item = (f64, f64)> + Clone) -> Option<(f64, f64)> {
+    const BINS: usize = 100;
+
+    let (min, max) = min_max(samples.clone());
+    if max <= min {
+        return None;
+    }
+
+    // Count and sum of the samples in each bin
+    let mut histogram = [(0usize, 0.0); BINS];
+    for (_, v) in samples {
+        let bin = ((v - min) / (max - min) * BINS as f64) as usize;
+        let (count, sum) = &mut histogram[bin.min(BINS - 1)];
+        *count += 1;
+        *sum += v;
+    }
+
+    let mode = |bins: std::ops::Range<usize>| {
+        let (count, sum) = bins
+            .map(|bin| histogram[bin])
+            .max_by_key(|(count, _)| *count)
+            .filter(|(count, _)| *count > 0)?;
+        Some(sum / count as f64)
+    };
+    Some((mode(0..BINS / 2)?, mode(BINS / 2..BINS)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analog(samples: &[(WaveformTime, f64)]) -> AnalogPoints {
+        let mut points = AnalogPoints::new();
+        for &(time, value) in samples {
+            points.push(time, value);
+        }
+        points
+    }
+
+    fn logic(levels: &[(WaveformTime, LogicState)]) -> DiscretePoints {
+        let mut points = DiscretePoints::default();
+        for &(time, state) in levels {
+            let label = Some(format!("{state:?}"));
+            let kind = DiscreteTransitionKind::Logic(state);
+            points.push_box(time, label, egui::Color32::WHITE, kind);
+        }
+        points
+    }
+
+    fn clock() -> DiscretePoints {
+        use LogicState::{High, Low};
+        logic(&[
+            (0, Low),
+            (10, High),
+            (15, Low),
+            (20, High),
+            (25, Low),
+            (30, High),
+        ])
+    }
+
+    #[test]
+    fn ramp_is_integrated_like_it_is_drawn() {
+        let points = analog(&[(0, 0.0), (10, 10.0)]);
+        let m = AnalogMeasurements::measure(&points, Interpolation::Linear, 0, 10).unwrap();
+
+        assert_eq!((m.min, m.max, m.peak_to_peak()), (0.0, 10.0, 10.0));
+        assert_eq!(m.mean, 5.0);
+        assert!((m.rms - (100.0f64 / 3.0).sqrt()).abs() < 1e-9);
+        assert_eq!((m.delta_y, m.slope, m.area), (10.0, Some(1.0), Some(50.0)));
+
+        let held = AnalogMeasurements::measure(&points, Interpolation::PreviousHold, 0, 10);
+        assert_eq!(held.unwrap().area, Some(0.0));
+    }
+
+    #[test]
+    fn window_edges_are_interpolated() {
+        let points = analog(&[(0, 0.0), (10, 10.0)]);
+        let m = AnalogMeasurements::measure(&points, Interpolation::Linear, 2, 6).unwrap();
+
+        assert_eq!((m.min, m.max, m.mean), (2.0, 6.0, 4.0));
+        assert_eq!(m.area, Some(16.0));
+    }
+
+    #[test]
+    fn reversed_windows_measure_the_same() {
+        let points = analog(&[(0, 1.0), (5, -2.0), (10, 4.0)]);
+        for interpolation in [
+            Interpolation::Linear,
+            Interpolation::PreviousHold,
+            Interpolation::Nearest,
+            Interpolation::Points,
+        ] {
+            assert_eq!(
+                AnalogMeasurements::measure(&points, interpolation, 10, 0),
+                AnalogMeasurements::measure(&points, interpolation, 0, 10),
+            );
+        }
+
+        let clock = clock();
+        assert_eq!(
+            PeriodicMeasurements::of_discrete(&clock, 30, 0),
+            PeriodicMeasurements::of_discrete(&clock, 0, 30),
+        );
+
+        let events = WaveformEvents::default();
+        let entity_path = EntityPath::from("D/clk");
+        assert_eq!(
+            DiscreteCounts::count(&clock, &events, &entity_path, Radix::default(), 30, 0),
+            DiscreteCounts::count(&clock, &events, &entity_path, Radix::default(), 0, 30),
+        );
+    }
+
+    #[test]
+    fn points_are_averaged_over_samples() {
+        let points = analog(&[(0, 1.0), (10, 3.0)]);
+        let m = AnalogMeasurements::measure(&points, Interpolation::Points, 0, 10).unwrap();
+
+        assert_eq!(m.mean, 2.0);
+        assert!((m.rms - 5.0f64.sqrt()).abs() < 1e-9);
+        assert_eq!(m.area, None);
+    }
+
+    #[test]
+    fn empty_window_has_no_measurements() {
+        let empty = AnalogPoints::new();
+        assert_eq!(
+            AnalogMeasurements::measure(&empty, Interpolation::Linear, 0, 10),
+            None
+        );
+
+        // Past the last sample there is no value
+        let points = analog(&[(0, 0.0), (10, 10.0)]);
+        assert_eq!(
+            AnalogMeasurements::measure(&points, Interpolation::Linear, 20, 30),
+            None
+        );
+
+        assert_eq!(PeriodicMeasurements::of_analog(&empty, 0, 10), None);
+        assert_eq!(
+            PeriodicMeasurements::of_discrete(&DiscretePoints::default(), 0, 10),
+            None
+        );
+    }
+
+    #[test]
+    fn zero_width_window_has_no_slope() {
+        let points = analog(&[(0, 0.0), (10, 10.0)]);
+        let m = AnalogMeasurements::measure(&points, Interpolation::Linear, 5, 5).unwrap();
+
+        assert_eq!((m.min, m.max, m.mean), (5.0, 5.0, 5.0));
+        assert_eq!((m.slope, m.area), (None, None));
+    }
+
+    #[test]
+    fn transitions_are_counted_per_label() {
+        let counts = DiscreteCounts::count(
+            &clock(),
+            &WaveformEvents::default(),
+            &EntityPath::from("D/clk"),
+            Radix::default(),
+            10,
+            25,
+        );
+
+        assert_eq!(counts.transitions["High"], 2);
+        assert_eq!(counts.transitions["Low"], 2);
+        assert_eq!((counts.total_transitions(), counts.total_events()), (4, 0));
+    }
+
+    #[test]
+    fn clock_period_and_duty_cycle() {
+        let periodic = PeriodicMeasurements::of_discrete(&clock(), 0, 30).unwrap();
+        assert_eq!(periodic.period, 10.0);
+        assert_eq!(periodic.frequency(), 0.1);
+        assert_eq!(periodic.pulse_width, Some(5.0));
+        assert_eq!(periodic.duty_cycle(), Some(0.5));
+
+        // The state in effect at the window start counts as the level before the first edge
+        let periodic = PeriodicMeasurements::of_discrete(&clock(), 12, 30).unwrap();
+        assert_eq!((periodic.period, periodic.pulse_width), (10.0, Some(5.0)));
+    }
+
+    #[test]
+    fn period_needs_two_rising_edges() {
+        let one_edge = [(0.0, false), (10.0, true), (15.0, false)];
+        assert_eq!(PeriodicMeasurements::from_levels(one_edge), None);
+
+        let no_edge = [(0.0, true), (10.0, true)];
+        assert_eq!(PeriodicMeasurements::from_levels(no_edge), None);
+
+        // Starting high, the first level doesn't count as a rising edge
+        let starts_high = [(0.0, true), (10.0, false), (20.0, true)];
+        assert_eq!(PeriodicMeasurements::from_levels(starts_high), None);
+
+        let last_pulse_open = [(0.0, false), (10.0, true), (15.0, false), (20.0, true)];
+        let periodic = PeriodicMeasurements::from_levels(last_pulse_open).unwrap();
+        assert_eq!((periodic.period, periodic.pulse_width), (10.0, Some(5.0)));
+    }
+
+    #[test]
+    fn analog_period_is_measured_at_the_middle_crossings() {
+        let values = [0.0, 0.0, 10.0, 10.0, 0.0, 0.0, 10.0, 10.0, 0.0, 0.0, 10.0];
+        let samples = values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i as WaveformTime, *v))
+            .collect_vec();
+        let points = analog(&samples);
+
+        let periodic = PeriodicMeasurements::of_analog(&points, 0, 10).unwrap();
+        assert_eq!(periodic.period, 4.0);
+        assert_eq!(periodic.pulse_width, Some(2.0));
+
+        let flat = analog(&[(0, 1.0), (10, 1.0)]);
+        assert_eq!(PeriodicMeasurements::of_analog(&flat, 0, 10), None);
+    }
+
+    #[test]
+    fn step_edge_is_measured() {
+        let samples = (0..10)
+            .map(|t| (t, if t < 5 { 0.0 } else { 10.0 }))
+            .collect_vec();
+        let points = analog(&samples);
+
+        let edges = AnalogEdges::find(&points, 0, 9).unwrap();
+        let edge = EdgeMeasurements::measure(&points, Interpolation::Linear, 0, 9, &edges, 0);
+        let edge = edge.unwrap();
+        assert!(edge.rising);
+
+        // 10 % to 90 % of a one sample step, settled once within the band after the middle
+        let transition_time = edge.transition_time.unwrap();
+        assert!((transition_time - 0.8).abs() < 1e-9, "{transition_time}");
+        assert_eq!((edge.overshoot, edge.undershoot), (0.0, 0.0));
+        let settling_time = edge.settling_time.unwrap();
+        assert!((settling_time - 0.48).abs() < 1e-9, "{settling_time}");
+
+        let flat = analog(&[(0, 1.0), (10, 1.0)]);
+        assert_eq!(AnalogEdges::find(&flat, 0, 10), None);
+    }
+
+    #[test]
+    fn state_levels_are_the_mean_of_the_fullest_bins() {
+        let values = [1.0, 1.002, 1.001, 3.0, 5.0, 4.998, 4.999, 4.997];
+        let samples = values.iter().enumerate().map(|(t, v)| (t as f64, *v));
+
+        let (low, high) = state_levels(samples).unwrap();
+        assert!((low - 1.001).abs() < 1e-9, "{low}");
+        assert!((high - 4.9985).abs() < 1e-9, "{high}");
+
+        assert_eq!(state_levels([(0.0, 2.0), (1.0, 2.0)].into_iter()), None);
+    }
+
+    #[test]
+    fn nearest_edge_is_on_either_side_of_the_cursor() {
+        // Edges at 9.5, 19.5 and 29.5
+        let samples = (0..40)
+            .map(|t| (t, if (t / 10) % 2 == 1 { 1.0 } else { 0.0 }))
+            .collect_vec();
+        let points = analog(&samples);
+        let edges = AnalogEdges::find(&points, 0, 39).unwrap();
+
+        assert_eq!(edges.nearest(0), Some(0));
+        assert_eq!(edges.nearest(13), Some(0));
+        assert_eq!(edges.nearest(17), Some(1));
+        assert_eq!(edges.nearest(100), Some(2));
+
+        let falling = EdgeMeasurements::measure(
+            &points,
+            Interpolation::Linear,
+            0,
+            39,
+            &edges,
+            edges.nearest(21).unwrap(),
+        );
+        assert!(falling.is_some_and(|edge| !edge.rising));
+    }
+
+    #[test]
+    fn segment_crossing_follows_interpolation() {
+        let (a, b) = ((0.0, 0.0), (10.0, 10.0));
+        assert_eq!(segment_crossing(Interpolation::Linear, a, b, 2.5), 2.5);
+        assert_eq!(
+            segment_crossing(Interpolation::PreviousHold, a, b, 2.5),
+            10.0
+        );
+        assert_eq!(segment_crossing(Interpolation::Nearest, a, b, 2.5), 5.0);
+    }
+}
@@ -0,0 +1,720 @@
+use std::{collections::HashSet, ops::Bound};
+
+use itertools::Itertools;
+use re_log_types::{ResolvedTimeRange, TimeInt};
+use re_query::QueryError;
+use re_sdk::{EntityPath, Loggable};
+use re_types::ComponentName;
+
+use crate::{
+    measurements::segment_crossing,
+    series_cache::CachedSeries,
+    types::components::{
+        BusValue, DiscreteState, Event, EventSpan, LogicBit, LogicState, SampleBlock, Scalar,
+        TextState,
+    },
+    view_state::Radix,
+    visualizer_system::RowLoader,
+    DiscreteTransition, DiscreteTransitionKind, WaveformEvents, WaveformSeries, WaveformTime,
+};
+
+/// What the search bar looks for
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SearchKind {
+    #[default]
+    RisingEdge,
+    FallingEdge,
+    State,
+    Threshold,
+    Event,
+    /// Any analog sample or discrete transition, stepped through with the arrow keys rather than
+    /// offered in the search bar
+    Sample,
+}
+
+impl SearchKind {
+    pub const ALL: [SearchKind; 5] = [
+        SearchKind::RisingEdge,
+        SearchKind::FallingEdge,
+        SearchKind::State,
+        SearchKind::Threshold,
+        SearchKind::Event,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SearchKind::RisingEdge => "Rising edge",
+            SearchKind::FallingEdge => "Falling edge",
+            SearchKind::State => "State",
+            SearchKind::Threshold => "Threshold",
+            SearchKind::Event => "Event",
+            SearchKind::Sample => "Sample",
+        }
+    }
+
+    /// Hint of the text searched for, kinds without one match on any series
+    pub fn hint(self) -> Option<&'static str> {
+        match self {
+            SearchKind::RisingEdge | SearchKind::FallingEdge | SearchKind::Sample => None,
+            SearchKind::State => Some("State label, any when empty"),
+            SearchKind::Threshold => Some("Value crossed"),
+            SearchKind::Event => Some("Event label, any when empty"),
+        }
+    }
+}
+
+/// Input of the search bar of a view
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchQuery {
+    pub kind: SearchKind,
+    pub text: String,
+}
+
+/// Condition the cursor is moved to the next or previous match of
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SearchCondition<'a> {
+    /// Logic bit going to `1`, bus value increasing, or state leaving the normal one
+    RisingEdge,
+    /// Logic bit going to `0`, bus value decreasing, or state returning to the normal one
+    FallingEdge,
+    /// Transition to a state labelled so, or to any state when empty
+    State(&'a str),
+    /// Analog series crossing the value in either direction
+    Threshold(f64),
+    /// Event labelled so, or any event when empty
+    Event(&'a str),
+    /// Any analog sample or discrete transition, for stepping through them
+    Sample,
+}
+
+impl SearchQuery {
+    /// Condition searched for, there is none while the threshold isn't a number
+    pub fn condition(&self) -> Option<SearchCondition<'_>> {
+        let text = self.text.trim();
+        Some(match self.kind {
+            SearchKind::RisingEdge => SearchCondition::RisingEdge,
+            SearchKind::FallingEdge => SearchCondition::FallingEdge,
+            SearchKind::State => SearchCondition::State(text),
+            SearchKind::Threshold => SearchCondition::Threshold(text.parse().ok()?),
+            SearchKind::Event => SearchCondition::Event(text),
+            SearchKind::Sample => SearchCondition::Sample,
+        })
+    }
+}
+
+impl SearchCondition<'_> {
+    /// Components of which an entity must have any for its rows to match the condition
+    pub fn component_names(self) -> Vec<ComponentName> {
+        let analog = [Scalar::name(), SampleBlock::name()];
+        // Text states are always drawn as boxes, so they have no edges
+        let edges = [DiscreteState::name(), BusValue::name(), LogicBit::name()];
+
+        match self {
+            SearchCondition::RisingEdge | SearchCondition::FallingEdge => edges.to_vec(),
+            SearchCondition::State(_) => edges.into_iter().chain([TextState::name()]).collect(),
+            SearchCondition::Threshold(_) => analog.to_vec(),
+            SearchCondition::Event(_) => vec![Event::name(), EventSpan::name()],
+            SearchCondition::Sample => analog
+                .into_iter()
+                .chain(edges)
+                .chain([TextState::name()])
+                .collect(),
+        }
+    }
+}
+
+/// Search posted by a view for its visualizer to run over the store, as the view only holds the
+/// rows loaded for the visible window
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchRequest {
+    pub query: SearchQuery,
+    /// Entities searched, any when empty
+    pub targets: HashSet<EntityPath>,
+    pub radix: Radix,
+    pub from: WaveformTime,
+    pub forward: bool,
+}
+
+impl SearchRequest {
+    /// Search from the cursor at `current_time`, or from the start or end of the timeline when
+    /// there is none
+    pub fn new(
+        query: SearchQuery,
+        targets: HashSet<EntityPath>,
+        radix: Radix,
+        current_time: Option<WaveformTime>,
+        forward: bool,
+    ) -> Self {
+        let from = current_time.unwrap_or(if forward {
+            WaveformTime::MIN
+        } else {
+            WaveformTime::MAX
+        });
+        Self {
+            query,
+            targets,
+            radix,
+            from,
+            forward,
+        }
+    }
+}
+
+/// Finds the match of `request` among the rows of the entity `loader` queries, within its
+/// visible history and not past `limit`.
+///
+/// Rows are loaded a block at a time in the search direction, the first `block_width` wide and
+/// every next one twice as wide, until one holds a match. Blocks are loaded with the rows in
+/// effect at their start, so matches across block boundaries are found too
+pub fn find_in_store(
+    loader: &RowLoader<'_>,
+    entity_path: &EntityPath,
+    request: &SearchRequest,
+    block_width: WaveformTime,
+    limit: Option<WaveformTime>,
+) -> Result<Option<WaveformTime>, QueryError> {
+    let Some(condition) = request.query.condition() else {
+        return Ok(None);
+    };
+
+    let history_range = loader.history_range();
+    let (history_min, history_max) = (history_range.min().as_i64(), history_range.max().as_i64());
+
+    let find_in = |min: WaveformTime, max: WaveformTime| -> Result<_, QueryError> {
+        let range = ResolvedTimeRange::new(TimeInt::new_temporal(min), TimeInt::new_temporal(max));
+        let block = CachedSeries::load_range(loader, entity_path.clone(), range)?;
+
+        let mut events = WaveformEvents::default();
+        for (time, markers) in block.events() {
+            for marker in markers {
+                events.push(*time, marker.clone());
+            }
+        }
+
+        Ok(find(
+            block
+                .series()
+                .collect_vec()
+                .iter()
+                .map(|series| series.as_ref()),
+            &events,
+            &request.targets,
+            condition,
+            request.radix,
+            request.from,
+            request.forward,
+        ))
+    };
+
+    let mut width = block_width.max(1);
+    if request.forward {
+        let end = limit.map_or(history_max, |limit| limit.min(history_max));
+        let mut start = request.from.max(history_min);
+        while start <= end {
+            let block_end = start.saturating_add(width).min(end);
+            if let Some(time) = find_in(start, block_end)? {
+                return Ok(Some(time));
+            }
+            if block_end == end {
+                break;
+            }
+            start = block_end + 1;
+            width = width.saturating_mul(2);
+        }
+    } else {
+        let start = limit.map_or(history_min, |limit| limit.max(history_min));
+        let mut end = request.from.min(history_max);
+        while start <= end {
+            let block_start = end.saturating_sub(width).max(start);
+            if let Some(time) = find_in(block_start, end)? {
+                return Ok(Some(time));
+            }
+            if block_start == start {
+                break;
+            }
+            end = block_start - 1;
+            width = width.saturating_mul(2);
+        }
+    }
+
+    Ok(None)
+}
+
+/// Finds the first match of `condition` after `from`, or the last one before it when not
+/// `forward`, among `series` and `events` of `targets`, or of any entity when there are none
+pub fn find<'a>(
+    series: impl IntoIterator<Item = &'a WaveformSeries>,
+    events: &WaveformEvents,
+    targets: &HashSet<EntityPath>,
+    condition: SearchCondition<'_>,
+    radix: Radix,
+    from: WaveformTime,
+    forward: bool,
+) -> Option<WaveformTime> {
+    let is_target = |entity_path: &EntityPath| targets.is_empty() || targets.contains(entity_path);
+
+    let matches = match condition {
+        SearchCondition::Event(label) => vec![find_event(events, &is_target, label, from, forward)],
+        _ => series
+            .into_iter()
+            .filter(|series| is_target(&series.entity_path))
+            .map(|series| match condition {
+                SearchCondition::Threshold(level) => find_crossing(series, level, from, forward),
+                SearchCondition::Sample => find_sample(series, from, forward),
+                _ => find_transition(series, condition, radix, from, forward),
+            })
+            .collect_vec(),
+    };
+
+    let matches = matches.into_iter().flatten();
+    if forward {
+        matches.min()
+    } else {
+        matches.max()
+    }
+}
+
+fn find_transition(
+    series: &WaveformSeries,
+    condition: SearchCondition<'_>,
+    radix: Radix,
+    from: WaveformTime,
+    forward: bool,
+) -> Option<WaveformTime> {
+    let transitions = &series.discrete_points.transitions;
+    let previous = |time: WaveformTime| transitions.range(..time).next_back().map(|(_, t)| t);
+    let is_match = |time: WaveformTime, transition: &DiscreteTransition| {
+        transition_matches(previous(time), transition, condition, radix)
+    };
+
+    let found = if forward {
+        transitions
+            .range((Bound::Excluded(from), Bound::Unbounded))
+            .find(|(time, transition)| is_match(**time, transition))
+    } else {
+        transitions
+            .range(..from)
+            .rev()
+            .find(|(time, transition)| is_match(**time, transition))
+    };
+    found.map(|(time, _)| *time)
+}
+
+fn transition_matches(
+    previous: Option<&DiscreteTransition>,
+    transition: &DiscreteTransition,
+    condition: SearchCondition<'_>,
+    radix: Radix,
+) -> bool {
+    use DiscreteTransitionKind::{Box, Bus, Line, Logic};
+
+    // States other than logic bits and bus values rise when leaving the normal state, drawn as a
+    // line, and fall when returning to it
+    let previous_kind = previous.map(|previous| &previous.kind);
+    match condition {
+        SearchCondition::RisingEdge => match (previous_kind, &transition.kind) {
+            (Some(Logic(state)), Logic(LogicState::High)) => *state != LogicState::High,
+            (Some(Bus { value: a, .. }), Bus { value: b, .. }) => b > a,
+            (Some(Line), Box) => true,
+            _ => false,
+        },
+        SearchCondition::FallingEdge => match (previous_kind, &transition.kind) {
+            (Some(Logic(state)), Logic(LogicState::Low)) => *state != LogicState::Low,
+            (Some(Bus { value: a, .. }), Bus { value: b, .. }) => b < a,
+            (Some(Box), Line) => true,
+            _ => false,
+        },
+        SearchCondition::State(label) => {
+            label.is_empty() || transition.display_label(radix).as_deref() == Some(label)
+        }
+        SearchCondition::Threshold(_) | SearchCondition::Event(_) | SearchCondition::Sample => {
+            false
+        }
+    }
+}
+
+/// Time of the next or previous analog sample or discrete transition of `series`
+fn find_sample(series: &WaveformSeries, from: WaveformTime, forward: bool) -> Option<WaveformTime> {
+    let analog = &series.analog_points.points;
+    let discrete = &series.discrete_points.transitions;
+
+    if forward {
+        let after = (Bound::Excluded(from), Bound::Unbounded);
+        let analog = analog.range(after).next().map(|(t, _)| *t);
+        let discrete = discrete.range(after).next().map(|(t, _)| *t);
+        analog.into_iter().chain(discrete).min()
+    } else {
+        let analog = analog.range(..from).next_back().map(|(t, _)| *t);
+        let discrete = discrete.range(..from).next_back().map(|(t, _)| *t);
+        analog.into_iter().chain(discrete).max()
+    }
+}
+
+/// Time of the next or previous crossing of `level` by the analog points of `series`, rounded
+/// away from `from`
+fn find_crossing(
+    series: &WaveformSeries,
+    level: f64,
+    from: WaveformTime,
+    forward: bool,
+) -> Option<WaveformTime> {
+    let points = &series.analog_points.points;
+    let crossing = |(t1, a): (WaveformTime, f64), (t2, b): (WaveformTime, f64)| {
+        ((a < level) != (b < level))
+            .then(|| segment_crossing(series.interpolation, (t1 as f64, a), (t2 as f64, b), level))
+    };
+
+    // Segments from the one holding `from` on
+    if forward {
+        let samples = points
+            .range(..=from)
+            .next_back()
+            .into_iter()
+            .chain(points.range((Bound::Excluded(from), Bound::Unbounded)))
+            .map(|(t, p)| (*t, p.value));
+        samples
+            .tuple_windows()
+            .filter_map(|(a, b)| crossing(a, b))
+            .map(|time| time.ceil() as WaveformTime)
+            .find(|time| *time > from)
+    } else {
+        let samples = points
+            .range(from..)
+            .next()
+            .into_iter()
+            .chain(points.range(..from).rev())
+            .map(|(t, p)| (*t, p.value));
+        samples
+            .tuple_windows()
+            .filter_map(|(b, a)| crossing(a, b))
+            .map(|time| time.floor() as WaveformTime)
+            .find(|time| *time < from)
+    }
+}
+
+fn find_event(
+    events: &WaveformEvents,
+    is_target: &impl Fn(&EntityPath) -> bool,
+    label: &str,
+    from: WaveformTime,
+    forward: bool,
+) -> Option<WaveformTime> {
+    let is_match = |(_, markers): &(&WaveformTime, &Vec<crate::EventMarker>)| {
+        markers.iter().any(|marker| {
+            is_target(&marker.entity_path)
+                && (label.is_empty() || marker.label.as_deref() == Some(label))
+        })
+    };
+
+    let found = if forward {
+        events
+            .event_markers
+            .range((Bound::Excluded(from), Bound::Unbounded))
+            .find(is_match)
+    } else {
+        events.event_markers.range(..from).rev().find(is_match)
+    };
+    found.map(|(time, _)| *time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AnalogPoints, DiscretePoints, EventMarker};
+
+    fn series(
+        entity_path: &str,
+        analog: &[(WaveformTime, f64)],
+        discrete: &[(WaveformTime, DiscreteTransitionKind, &str)],
+    ) -> WaveformSeries {
+        let mut analog_points = AnalogPoints::new();
+        for &(time, value) in analog {
+            analog_points.push(time, value);
+        }
+
+        let mut discrete_points = DiscretePoints::default();
+        for (time, kind, label) in discrete {
+            let label = Some(label.to_string());
+            discrete_points.push_box(*time, label, egui::Color32::WHITE, kind.clone());
+        }
+
+        let times = analog.iter().map(|(t, _)| *t);
+        let times = times
+            .chain(discrete.iter().map(|(t, _, _)| *t))
+            .collect_vec();
+        WaveformSeries {
+            entity_path: EntityPath::from(entity_path),
+            instance: None,
+            min_time: times.iter().copied().min().unwrap_or(i64::MAX),
+            max_time: times.iter().copied().max().unwrap_or(i64::MIN),
+            analog_points,
+            discrete_points,
+            color: egui::Color32::WHITE,
+            unit: None,
+            interpolation: Default::default(),
+            queried_range: (i64::MIN, i64::MAX),
+            generation: 0,
+        }
+    }
+
+    fn clock() -> WaveformSeries {
+        use LogicState::{High, Low};
+        let logic = |time, state| (time, DiscreteTransitionKind::Logic(state), "");
+        series(
+            "D/clk",
+            &[],
+            &[
+                logic(0, Low),
+                logic(10, High),
+                logic(20, Low),
+                logic(30, High),
+            ],
+        )
+    }
+
+    fn find_in(
+        series: &[WaveformSeries],
+        condition: SearchCondition<'_>,
+        from: WaveformTime,
+        forward: bool,
+    ) -> Option<WaveformTime> {
+        let events = WaveformEvents::default();
+        let targets = HashSet::new();
+        find(
+            series,
+            &events,
+            &targets,
+            condition,
+            Radix::default(),
+            from,
+            forward,
+        )
+    }
+
+    #[test]
+    fn logic_edges() {
+        let clock = [clock()];
+        assert_eq!(
+            find_in(&clock, SearchCondition::RisingEdge, 0, true),
+            Some(10)
+        );
+        assert_eq!(
+            find_in(&clock, SearchCondition::RisingEdge, 10, true),
+            Some(30)
+        );
+        assert_eq!(
+            find_in(&clock, SearchCondition::RisingEdge, 30, false),
+            Some(10)
+        );
+        assert_eq!(
+            find_in(&clock, SearchCondition::FallingEdge, 0, true),
+            Some(20)
+        );
+        assert_eq!(
+            find_in(&clock, SearchCondition::FallingEdge, 20, true),
+            None
+        );
+    }
+
+    #[test]
+    fn bus_edges_follow_the_value() {
+        let bus = |time, value| (time, DiscreteTransitionKind::Bus { value, width: 8 }, "");
+        let bus = [series("B/bus", &[], &[bus(0, 1), bus(10, 5), bus(20, 2)])];
+        assert_eq!(
+            find_in(&bus, SearchCondition::RisingEdge, 0, true),
+            Some(10)
+        );
+        assert_eq!(
+            find_in(&bus, SearchCondition::FallingEdge, 0, true),
+            Some(20)
+        );
+    }
+
+    #[test]
+    fn state_edges_leave_and_return_to_the_normal_state() {
+        use DiscreteTransitionKind::{Box, Line};
+        let states = [series(
+            "S/state",
+            &[],
+            &[
+                (0, Line, "Idle"),
+                (10, Box, "Busy"),
+                (15, Box, "Error"),
+                (20, Line, "Idle"),
+            ],
+        )];
+        assert_eq!(
+            find_in(&states, SearchCondition::RisingEdge, 0, true),
+            Some(10)
+        );
+        assert_eq!(
+            find_in(&states, SearchCondition::RisingEdge, 10, true),
+            None
+        );
+        assert_eq!(
+            find_in(&states, SearchCondition::FallingEdge, 0, true),
+            Some(20)
+        );
+        assert_eq!(
+            find_in(&states, SearchCondition::State("Error"), 0, true),
+            Some(15)
+        );
+        assert_eq!(
+            find_in(&states, SearchCondition::State(""), 10, true),
+            Some(15)
+        );
+    }
+
+    #[test]
+    fn threshold_crossings_in_both_directions() {
+        let triangle = [series("A/y", &[(0, 0.0), (10, 10.0), (20, 0.0)], &[])];
+        let threshold = SearchCondition::Threshold(5.0);
+        assert_eq!(find_in(&triangle, threshold, 0, true), Some(5));
+        assert_eq!(find_in(&triangle, threshold, 5, true), Some(15));
+        assert_eq!(find_in(&triangle, threshold, 15, true), None);
+        assert_eq!(find_in(&triangle, threshold, 20, false), Some(15));
+        assert_eq!(find_in(&triangle, threshold, 15, false), Some(5));
+        assert_eq!(
+            find_in(&triangle, SearchCondition::Threshold(11.0), 0, true),
+            None
+        );
+    }
+
+    #[test]
+    fn samples_of_every_series_are_stepped_through() {
+        let all = [
+            series("A/y", &[(0, 0.0), (10, 1.0)], &[]),
+            series("D/d", &[], &[(5, DiscreteTransitionKind::Box, "On")]),
+        ];
+        assert_eq!(find_in(&all, SearchCondition::Sample, 0, true), Some(5));
+        assert_eq!(find_in(&all, SearchCondition::Sample, 5, true), Some(10));
+        assert_eq!(find_in(&all, SearchCondition::Sample, 10, false), Some(5));
+        assert_eq!(find_in(&all, SearchCondition::Sample, 10, true), None);
+        assert_eq!(find_in(&[], SearchCondition::Sample, 0, true), None);
+    }
+
+    #[test]
+    fn only_targets_are_searched() {
+        let all = [series("A/y", &[(0, 0.0), (10, 1.0)], &[]), clock()];
+        let targets = HashSet::from([EntityPath::from("D/clk")]);
+        let found = |from, forward| {
+            let events = WaveformEvents::default();
+            let condition = SearchCondition::Sample;
+            find(
+                &all,
+                &events,
+                &targets,
+                condition,
+                Radix::default(),
+                from,
+                forward,
+            )
+        };
+        assert_eq!(found(0, true), Some(10));
+        assert_eq!(found(10, true), Some(20));
+    }
+
+    #[test]
+    fn events_by_label_and_entity() {
+        let mut events = WaveformEvents::default();
+        for (time, entity_path, label) in [(10, "E/a", "X"), (20, "E/b", "Y")] {
+            events.push(
+                time,
+                EventMarker {
+                    entity_path: EntityPath::from(entity_path),
+                    label: Some(label.to_owned()),
+                    color: egui::Color32::WHITE,
+                    duration: None,
+                },
+            );
+        }
+
+        let find_event = |targets: &HashSet<EntityPath>, label, from, forward| {
+            let condition = SearchCondition::Event(label);
+            find(
+                &[],
+                &events,
+                targets,
+                condition,
+                Radix::default(),
+                from,
+                forward,
+            )
+        };
+        let any = HashSet::new();
+        let a = HashSet::from([EntityPath::from("E/a")]);
+
+        assert_eq!(find_event(&any, "Y", 0, true), Some(20));
+        assert_eq!(find_event(&any, "", 20, false), Some(10));
+        assert_eq!(find_event(&any, "Z", 0, true), None);
+        assert_eq!(find_event(&a, "", 0, true), Some(10));
+        assert_eq!(find_event(&a, "", 10, true), None);
+    }
+
+    #[test]
+    fn threshold_needs_a_number() {
+        let query = |kind, text: &str| SearchQuery {
+            kind,
+            text: text.to_owned(),
+        };
+        assert_eq!(
+            query(SearchKind::Threshold, " 2.5 ").condition(),
+            Some(SearchCondition::Threshold(2.5))
+        );
+        assert_eq!(query(SearchKind::Threshold, "high").condition(), None);
+        assert_eq!(
+            query(SearchKind::State, " Busy ").condition(),
+            Some(SearchCondition::State("Busy"))
+        );
+    }
+
+    #[test]
+    fn samples_are_only_stepped_through_with_the_arrow_keys() {
+        assert!(!SearchKind::ALL.contains(&SearchKind::Sample));
+
+        let query = SearchQuery {
+            kind: SearchKind::Sample,
+            text: "ignored".to_owned(),
+        };
+        assert_eq!(query.condition(), Some(SearchCondition::Sample));
+    }
+
+    #[test]
+    fn conditions_only_look_at_the_components_they_can_match() {
+        let edges = SearchCondition::RisingEdge.component_names();
+        assert!(edges.contains(&LogicBit::name()));
+        assert!(!edges.contains(&Scalar::name()));
+        assert!(!edges.contains(&TextState::name()));
+
+        let threshold = SearchCondition::Threshold(1.0).component_names();
+        assert!(threshold.contains(&SampleBlock::name()));
+        assert!(!threshold.contains(&DiscreteState::name()));
+
+        assert!(SearchCondition::State("")
+            .component_names()
+            .contains(&TextState::name()));
+        assert!(SearchCondition::Sample
+            .component_names()
+            .contains(&Scalar::name()));
+        assert!(!SearchCondition::Event("")
+            .component_names()
+            .contains(&BusValue::name()));
+    }
+
+    #[test]
+    fn request_starts_at_the_timeline_end_without_cursor() {
+        let request = |current_time, forward| {
+            let query = SearchQuery::default();
+            SearchRequest::new(
+                query,
+                HashSet::new(),
+                Radix::default(),
+                current_time,
+                forward,
+            )
+        };
+        assert_eq!(request(None, true).from, WaveformTime::MIN);
+        assert_eq!(request(None, false).from, WaveformTime::MAX);
+        assert_eq!(request(Some(7), false).from, 7);
+    }
+}
@@ -0,0 +1,598 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+};
+
+use re_data_store::{DataStore, StoreEvent, StoreSubscriber, StoreSubscriberHandle};
+use re_log_types::{ResolvedTimeRange, StoreId, TimeInt, Timeline};
+use re_query::QueryError;
+use re_sdk::{EntityPath, Loggable};
+use re_types::components::{AnnotationContext, ClearIsRecursive};
+use re_viewer_context::{auto_color_for_entity_path, SpaceViewId};
+
+use crate::{
+    diagnostics::{EntityDiagnostics, RowIssue},
+    visualizer_system::RowLoader,
+    AnalogPoints, EventMarker, WaveformSeries, WaveformTime,
+};
+
+/// A loaded range wider than this many visible windows is dropped and reloaded, to bound memory
+const MAX_LOADED_WINDOWS: i64 = 8;
+
+/// Changes to a store since they were last applied to the series cache
+#[derive(Default)]
+struct StoreChanges {
+    /// Earliest time rows were added at, per entity and timeline. Cached rows from there on are
+    /// reloaded, which also rescales them once a calibration row was added
+    added: HashMap<(EntityPath, Timeline), TimeInt>,
+
+    /// Entities whose rows have to be reloaded: static data changed, rows were garbage collected
+    /// or the entity was cleared
+    invalidated: HashSet<EntityPath>,
+
+    /// Cleared entities, whose children are invalidated as well
+    cleared: HashSet<EntityPath>,
+
+    /// Annotation contexts changed, labels and colors of every entity may have to be resolved
+    /// again
+    annotations_changed: bool,
+}
+
+/// Collects [`StoreChanges`] per store for the [`WaveformSeriesCache`]s.
+///
+/// Only stores with cached series are watched, events of other stores are dropped so that
+/// nothing piles up for recordings no waveform view shows
+#[derive(Default)]
+struct WaveformStoreSubscriber {
+    changes: HashMap<StoreId, StoreChanges>,
+}
+
+impl StoreSubscriber for WaveformStoreSubscriber {
+    fn name(&self) -> String {
+        "WaveformStoreSubscriber".to_owned()
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn on_events(&mut self, events: &[StoreEvent]) {
+        for event in events {
+            let Some(changes) = self.changes.get_mut(&event.store_id) else {
+                continue;
+            };
+            let diff = &event.diff;
+
+            if diff.cells.contains_key(&AnnotationContext::name()) {
+                changes.annotations_changed = true;
+            }
+
+            if matches!(diff.kind, re_data_store::StoreDiffKind::Deletion) || diff.times.is_empty()
+            {
+                changes.invalidated.insert(diff.entity_path.clone());
+            } else if diff.cells.contains_key(&ClearIsRecursive::name()) {
+                changes.cleared.insert(diff.entity_path.clone());
+            } else {
+                for (timeline, time) in &diff.times {
+                    changes
+                        .added
+                        .entry((diff.entity_path.clone(), *timeline))
+                        .and_modify(|earliest| *earliest = (*earliest).min(*time))
+                        .or_insert(*time);
+                }
+            }
+        }
+    }
+}
+
+static STORE_SUBSCRIBER: once_cell::sync::Lazy<StoreSubscriberHandle> =
+    once_cell::sync::Lazy::new(|| {
+        DataStore::register_subscriber(Box::<WaveformStoreSubscriber>::default())
+    });
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SeriesKey {
+    pub space_view_id: SpaceViewId,
+    pub entity_path: EntityPath,
+    pub timeline: Timeline,
+}
+
+/// Rows of one entity on one timeline, kept across frames and only extended by what changed
+pub struct CachedSeries {
+    entity_path: EntityPath,
+
+    /// One series per instance, see [`crate::visualizer_system::LoadedRows`]
+    channels: Vec<Arc<WaveformSeries>>,
+    events: BTreeMap<WaveformTime, Vec<EventMarker>>,
+
+    /// Issues of the loaded rows, and of static data
+    issues: BTreeMap<WaveformTime, Vec<RowIssue>>,
+    static_issues: Vec<RowIssue>,
+
+    /// Some of the data wasn't available yet when it was loaded
+    pending: bool,
+
+    /// Range every row of which has been loaded
+    loaded_range: Option<ResolvedTimeRange>,
+
+    /// Rows were added at or after this time since they were loaded
+    dirty_from: Option<WaveformTime>,
+
+    used_this_frame: bool,
+}
+
+impl CachedSeries {
+    fn new(entity_path: EntityPath) -> Self {
+        Self {
+            entity_path,
+            channels: vec![],
+            events: BTreeMap::new(),
+            issues: BTreeMap::new(),
+            static_issues: vec![],
+            pending: false,
+            loaded_range: None,
+            dirty_from: None,
+            used_this_frame: true,
+        }
+    }
+
+    /// Rows of `range` alone, loaded outside of the cache, e.g. to search them
+    pub fn load_range(
+        loader: &RowLoader<'_>,
+        entity_path: EntityPath,
+        range: ResolvedTimeRange,
+    ) -> Result<Self, QueryError> {
+        let mut cached = Self::new(entity_path);
+        cached.load(loader, range, true)?;
+        cached.loaded_range = Some(range);
+        Ok(cached)
+    }
+
+    fn invalidate(&mut self) {
+        self.channels.clear();
+        self.events.clear();
+        self.issues.clear();
+        self.static_issues.clear();
+        self.pending = false;
+        self.loaded_range = None;
+        self.dirty_from = None;
+    }
+
+    /// Channels loaded so far that hold any rows
+    pub fn series(&self) -> impl Iterator<Item = Arc<WaveformSeries>> + '_ {
+        self.channels
+            .iter()
+            .filter(|series| series.min_time <= series.max_time)
+            .cloned()
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = (&WaveformTime, &Vec<EventMarker>)> {
+        self.events.iter()
+    }
+
+    /// Everything that isn't drawn as logged in the loaded rows
+    pub fn diagnostics(&self) -> EntityDiagnostics {
+        EntityDiagnostics::new(
+            self.issues.values().flatten().chain(&self.static_issues),
+            self.channels.len(),
+            self.pending,
+        )
+    }
+
+    /// Loads whatever is missing for `window_range`: rows added since the last frame, and the
+    /// parts of the window that weren't loaded yet
+    pub fn update(
+        &mut self,
+        loader: &RowLoader<'_>,
+        window_range: ResolvedTimeRange,
+    ) -> Result<(), QueryError> {
+        let (window_min, window_max) = (window_range.min().as_i64(), window_range.max().as_i64());
+
+        let history_range = loader.history_range();
+        let (min_time, max_time) = self.time_bounds();
+
+        let reload = match self.loaded_range {
+            None => true,
+            Some(loaded) => {
+                let (loaded_min, loaded_max) = (loaded.min().as_i64(), loaded.max().as_i64());
+                let window_width = window_max.saturating_sub(window_min).max(1);
+
+                // Rows outside the visible history must not be drawn, and windows that don't
+                // overlap are cheaper to load from scratch
+                min_time < history_range.min().as_i64()
+                    || max_time > history_range.max().as_i64()
+                    || window_max < loaded_min
+                    || loaded_max < window_min
+                    || loaded_max.saturating_sub(loaded_min)
+                        > window_width.saturating_mul(MAX_LOADED_WINDOWS)
+            }
+        };
+
+        if reload {
+            self.invalidate();
+            self.load(loader, window_range, true)?;
+            self.loaded_range = Some(window_range);
+        } else if let Some(loaded) = self.loaded_range {
+            let (loaded_min, loaded_max) = (loaded.min().as_i64(), loaded.max().as_i64());
+
+            // Rows added since the last frame: drop everything from the earliest of them on and
+            // query it again
+            if let Some(dirty_from) = self.dirty_from.take() {
+                if dirty_from <= loaded_min {
+                    self.invalidate();
+                    self.load(loader, loaded, true)?;
+                } else if dirty_from <= loaded_max {
+                    self.truncate_from(dirty_from);
+                    self.load(
+                        loader,
+                        ResolvedTimeRange::new(TimeInt::new_temporal(dirty_from), loaded.max()),
+                        false,
+                    )?;
+                }
+            }
+
+            let mut loaded_range = loaded;
+
+            if window_min < loaded_min {
+                self.load(
+                    loader,
+                    ResolvedTimeRange::new(
+                        window_range.min(),
+                        TimeInt::new_temporal(loaded_min - 1),
+                    ),
+                    true,
+                )?;
+                loaded_range.set_min(window_range.min());
+            }
+
+            if window_max > loaded_max {
+                self.load(
+                    loader,
+                    ResolvedTimeRange::new(
+                        TimeInt::new_temporal(loaded_max + 1),
+                        window_range.max(),
+                    ),
+                    false,
+                )?;
+                loaded_range.set_max(window_range.max());
+            }
+
+            self.loaded_range = Some(loaded_range);
+        }
+
+        // Query again next frame, until all data is available
+        if self.pending {
+            self.loaded_range = None;
+        }
+
+        if let Some(loaded) = self.loaded_range {
+            for series in &mut self.channels {
+                Arc::make_mut(series).queried_range =
+                    (loaded.min().as_i64(), loaded.max().as_i64());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn load(
+        &mut self,
+        loader: &RowLoader<'_>,
+        range: ResolvedTimeRange,
+        extend_to_previous: bool,
+    ) -> Result<(), QueryError> {
+        let rows = loader.load(range, extend_to_previous)?;
+
+        // Every entity has at least one channel, even if it only holds events
+        self.ensure_channels(rows.channel_count().max(1));
+        let channel_count = self.channels.len();
+
+        // Rows are queried as a whole, replace what was loaded before
+        let times = rows.scalars.iter().map(|(time, _)| time);
+        let times = times.chain(rows.discretes.iter().map(|(time, _)| time));
+        for time in times.chain(rows.events.iter().map(|(time, _)| time)) {
+            self.issues.remove(time);
+        }
+
+        let mut push_issue = |time: WaveformTime, issue: RowIssue| {
+            self.issues.entry(time).or_default().push(issue);
+        };
+
+        for (time, values) in rows.scalars {
+            if values.len() < channel_count {
+                push_issue(
+                    time,
+                    RowIssue::MissingInstances {
+                        found: values.len(),
+                    },
+                );
+            }
+
+            for (instance, value) in values.into_iter().enumerate() {
+                Arc::make_mut(&mut self.channels[instance])
+                    .analog_points
+                    .push(time, value);
+            }
+        }
+
+        for (time, transitions) in rows.discretes {
+            if transitions.len() < channel_count {
+                push_issue(
+                    time,
+                    RowIssue::MissingInstances {
+                        found: transitions.len(),
+                    },
+                );
+            }
+
+            for (instance, transition) in transitions.into_iter().enumerate() {
+                match transition {
+                    Ok(transition) => {
+                        Arc::make_mut(&mut self.channels[instance])
+                            .discrete_points
+                            .transitions
+                            .insert(time, transition);
+                    }
+                    Err(issue) => push_issue(time, issue),
+                }
+            }
+        }
+
+        for (time, event_markers) in rows.events {
+            let mut markers = vec![];
+            for event_marker in event_markers {
+                match event_marker {
+                    Ok(event_marker) => markers.push(event_marker),
+                    Err(issue) => push_issue(time, issue),
+                }
+            }
+            self.events.insert(time, markers);
+        }
+
+        if !rows.init.is_empty() {
+            self.static_issues = rows
+                .init
+                .iter()
+                .filter_map(|init| init.clone().err())
+                .collect();
+
+            for (instance, series) in self.channels.iter_mut().enumerate() {
+                let init = rows
+                    .init
+                    .get(instance)
+                    .or_else(|| (rows.init.len() == 1).then(|| &rows.init[0]));
+                Arc::make_mut(series).discrete_points.init =
+                    init.and_then(|init| init.clone().ok());
+            }
+        }
+
+        if !rows.colors.is_empty() {
+            for (instance, series) in self.channels.iter_mut().enumerate() {
+                let color = rows
+                    .colors
+                    .get(instance)
+                    .or_else(|| (rows.colors.len() == 1).then(|| &rows.colors[0]));
+                if let Some(color) = color {
+                    if series.color != *color {
+                        Arc::make_mut(series).color = *color;
+                    }
+                }
+            }
+        }
+
+        // Unit and interpolation are the latest ones, which only a load reaching the end of the
+        // loaded range sees, however long before the load they were logged. They are reset to the
+        // defaults once no longer logged
+        let reaches_end = self
+            .loaded_range
+            .map_or(true, |loaded| range.max() >= loaded.max());
+        if reaches_end {
+            let interpolation = rows.interpolation.unwrap_or_default();
+            for series in &mut self.channels {
+                if series.unit != rows.unit || series.interpolation != interpolation {
+                    let series = Arc::make_mut(series);
+                    series.unit = rows.unit.clone();
+                    series.interpolation = interpolation;
+                }
+            }
+        }
+
+        self.pending |= rows.pending;
+
+        self.rows_changed();
+        Ok(())
+    }
+
+    /// Adds channels up to `count`, naming them by instance once there is more than one
+    fn ensure_channels(&mut self, count: usize) {
+        while self.channels.len() < count {
+            let instance = self.channels.len();
+            self.channels.push(Arc::new(WaveformSeries {
+                entity_path: self.entity_path.clone(),
+                instance: None,
+                min_time: i64::MAX,
+                max_time: i64::MIN,
+                analog_points: AnalogPoints::new(),
+                discrete_points: Default::default(),
+                color: channel_color(&self.entity_path, instance),
+                unit: None,
+                interpolation: Default::default(),
+                queried_range: (i64::MAX, i64::MIN),
+                generation: crate::next_generation(),
+            }));
+        }
+
+        if self.channels.len() > 1 {
+            for (instance, series) in self.channels.iter_mut().enumerate() {
+                if series.instance != Some(instance) {
+                    Arc::make_mut(series).instance = Some(instance);
+                }
+            }
+        }
+    }
+
+    /// Drops all rows at or after `time`
+    fn truncate_from(&mut self, time: WaveformTime) {
+        for series in &mut self.channels {
+            let series = Arc::make_mut(series);
+            series.analog_points.truncate_from(time);
+            series.discrete_points.transitions.split_off(&time);
+        }
+
+        self.events.split_off(&time);
+        self.issues.split_off(&time);
+
+        self.rows_changed();
+    }
+
+    /// Earliest and latest row over all channels and events
+    fn time_bounds(&self) -> (WaveformTime, WaveformTime) {
+        let min_time = self.channels.iter().map(|series| series.min_time).min();
+        let max_time = self.channels.iter().map(|series| series.max_time).max();
+        (min_time.unwrap_or(i64::MAX), max_time.unwrap_or(i64::MIN))
+    }
+
+    /// Updates the time bounds and generation of every channel after their rows changed
+    fn rows_changed(&mut self) {
+        let first_event = self.events.keys().next().copied();
+        let last_event = self.events.keys().next_back().copied();
+
+        for series in &mut self.channels {
+            let series = Arc::make_mut(series);
+
+            let firsts = [
+                series.analog_points.points.keys().next().copied(),
+                series.discrete_points.transitions.keys().next().copied(),
+                first_event,
+            ];
+            let lasts = [
+                series.analog_points.points.keys().next_back().copied(),
+                series
+                    .discrete_points
+                    .transitions
+                    .keys()
+                    .next_back()
+                    .copied(),
+                last_event,
+            ];
+
+            series.min_time = firsts.into_iter().flatten().min().unwrap_or(i64::MAX);
+            series.max_time = lasts.into_iter().flatten().max().unwrap_or(i64::MIN);
+            series.generation = crate::next_generation();
+        }
+    }
+}
+
+/// Color of a channel: the entity's own color for the first one
+pub fn channel_color(entity_path: &EntityPath, instance: usize) -> egui::Color32 {
+    let color = if instance == 0 {
+        auto_color_for_entity_path(entity_path)
+    } else {
+        auto_color_for_entity_path(
+            &entity_path.join(&EntityPath::from_single_string(instance.to_string())),
+        )
+    };
+    color.into()
+}
+
+/// Per recording cache of the rows loaded by the [`crate::visualizer_system::WaveformSystem`]
+#[derive(Default)]
+pub struct WaveformSeriesCache {
+    series: HashMap<SeriesKey, CachedSeries>,
+
+    /// Store the subscriber collects changes of for this cache
+    store_id: Option<StoreId>,
+}
+
+impl WaveformSeriesCache {
+    /// Applies the changes the store has seen since the last frame to the cached series, and
+    /// watches the store for the next frame
+    pub fn apply_store_changes(&mut self, store_id: &StoreId) {
+        self.store_id = Some(store_id.clone());
+
+        let Some(changes) = DataStore::with_subscriber_mut(
+            *STORE_SUBSCRIBER,
+            |subscriber: &mut WaveformStoreSubscriber| {
+                subscriber
+                    .changes
+                    .insert(store_id.clone(), StoreChanges::default())
+            },
+        ) else {
+            return;
+        };
+
+        // Changes of a store that wasn't watched were missed, reload everything
+        let Some(changes) = changes else {
+            for cached in self.series.values_mut() {
+                cached.invalidate();
+            }
+            return;
+        };
+
+        for (key, cached) in &mut self.series {
+            let entity_path = &key.entity_path;
+
+            let cleared = changes
+                .cleared
+                .iter()
+                .any(|cleared| entity_path.starts_with(cleared));
+
+            if changes.annotations_changed || cleared || changes.invalidated.contains(entity_path) {
+                cached.invalidate();
+            } else if let Some(time) = changes.added.get(&(entity_path.clone(), key.timeline)) {
+                let time = time.as_i64();
+                cached.dirty_from = Some(cached.dirty_from.map_or(time, |t| t.min(time)));
+            }
+        }
+    }
+
+    /// Cached series for `key`, created empty if it isn't cached yet
+    pub fn entry(&mut self, key: SeriesKey) -> &mut CachedSeries {
+        let entity_path = key.entity_path.clone();
+        let cached = self
+            .series
+            .entry(key)
+            .or_insert_with(|| CachedSeries::new(entity_path));
+
+        cached.used_this_frame = true;
+        cached
+    }
+
+    /// Stops collecting changes of the store, once no series of it are cached
+    fn unwatch_store(&mut self) {
+        if let Some(store_id) = self.store_id.take() {
+            DataStore::with_subscriber_mut(
+                *STORE_SUBSCRIBER,
+                |subscriber: &mut WaveformStoreSubscriber| subscriber.changes.remove(&store_id),
+            );
+        }
+    }
+}
+
+impl re_viewer_context::Cache for WaveformSeriesCache {
+    fn begin_frame(&mut self) {
+        // Make sure changes are collected from the first frame the cache is used on
+        once_cell::sync::Lazy::force(&STORE_SUBSCRIBER);
+
+        // Drop series that are no longer shown, e.g. after switching timelines
+        self.series
+            .retain(|_, cached| std::mem::replace(&mut cached.used_this_frame, false));
+
+        if self.series.is_empty() {
+            self.unwatch_store();
+        }
+    }
+
+    fn purge_memory(&mut self) {
+        self.series.clear();
+        self.unwatch_store();
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
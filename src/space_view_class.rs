@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     error::Error,
     ops::Bound,
 };
@@ -16,28 +16,43 @@ use re_viewer_context::{
     SpaceViewClass, SpaceViewSpawnHeuristics, SpaceViewState, SpaceViewStateExt,
 };
 
-use crate::visualizer_system::WaveformSystem;
+use crate::{
+    decimation::AnalogPyramidCache,
+    measurements::{
+        seconds_per_time_unit, AnalogMeasurements, DiscreteCounts, EdgeMeasurements,
+        MeasurementCache, PeriodicMeasurements,
+    },
+    search::{SearchKind, SearchQuery, SearchRequest},
+    series_cache::SeriesKey,
+    types::components::{Interpolation, LogicState},
+    units::{format_si, UnitAxis},
+    view_state::{DomainGrouping, PersistedViewState, Radix, ViewLayout},
+    visualizer_system::WaveformSystem,
+};
 
-use super::{annotation_context::AnnotationWaveformContext, DiscreteTransition, WaveformDomain};
+use super::{
+    annotation_context::AnnotationWaveformContext, DiscreteTransition, WaveformDomain, WaveformTime,
+};
 
-type PlotTime = f64;
-type PlotPositionIndex = usize;
 #[derive(Clone, Default)]
 pub struct WaveformSpaceViewState {
-    /// Total samples last viewed in the waveform
-    last_frame_sample_count: usize,
+    /// Time extent of the data last viewed in the waveform
+    last_frame_time_extent: Option<(WaveformTime, WaveformTime)>,
+
+    /// Plot bounds were reset while the queried window changed, reset again once it is loaded
+    reset_pending: bool,
 
-    /// Secondary marker position for the waveform
-    second_marker: Option<PlotTime>,
+    /// Warnings per entity from the last frame, for the selection panel
+    warnings: BTreeMap<re_log_types::EntityPath, Vec<String>>,
 
-    /// Domain order index
-    domain_index: HashMap<WaveformDomain, PlotPositionIndex>,
+    /// Sizes being dragged, written to the blueprint once the drag stops
+    layout_drag: Option<ViewLayout>,
 
-    /// Selected mode- displays only entities that are selected
-    selected_mode: SelectedMode,
+    /// Input of the search bar
+    search: SearchQuery,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum SelectedMode {
     Selected(HashSet<re_log_types::EntityPath>),
     #[default]
@@ -45,6 +60,14 @@ pub enum SelectedMode {
 }
 
 impl SelectedMode {
+    pub fn from_paths(paths: HashSet<re_log_types::EntityPath>) -> Self {
+        if paths.is_empty() {
+            SelectedMode::Unselected
+        } else {
+            SelectedMode::Selected(paths)
+        }
+    }
+
     pub fn selected(&self) -> bool {
         match self {
             SelectedMode::Selected(_) => true,
@@ -79,6 +102,9 @@ impl SpaceViewState for WaveformSpaceViewState {
     }
 }
 
+/// Drag and drop payload of a domain being reordered
+struct DraggedDomain(WaveformDomain);
+
 #[derive(Clone, Debug)]
 pub struct WaveformView;
 
@@ -115,11 +141,23 @@ impl Error for WaveformSpaceViewDrawError {}
 const CURSOR_TIME_TOLERANCE: i64 = 1; //1ns tolerance for cursor time
 const X_AXIS_FONT_SIZE_PX: f32 = 16.0; //Font size for x-axis labels (12pt)
 const DEFAULT_WAVEFORM_PADDING_PC: f64 = 0.2; //Percentage increase in waveform padding on reset
-const DEFAULT_SIDE_PANEL_WIDTH_PC: f32 = 0.3; //Percentage of width side panel occupies
+const MIN_SIDE_PANEL_WIDTH_PC: f32 = 0.1; //Minimum percentage of width side panel occupies when resized
+const MAX_SIDE_PANEL_WIDTH_PC: f32 = 0.7; //Maximum percentage of width side panel occupies when resized
+const MIN_LANE_HEIGHT: f32 = 24.0; //Minimum height of a domain row, taller views scroll
+const SPLITTER_GRAB_WIDTH: f32 = 3.0; //Distance from a splitter within which it can be dragged
 const DISCRETE_ALL_PADDING_PC: f64 = 0.95; //Padding for discrete waveform superimposed on continuous waveform
 const DISCRETE_BOX_PADDING_PC: f64 = 0.8; //Padding between discrete box waveforms
 const DISCRETE_STROKE_WIDTH_PC: f32 = 0.1; //Percent of stroke width of the discrete box for line
 const DISCRETE_STROKE_WIDTH_MIN: f32 = 1.0; //Minimum stroke width for line
+const SPAN_FILL_ALPHA: f32 = 0.15; //Opacity of the shading of an event span
+const SPAN_HIGHLIGHT_FILL_ALPHA: f32 = 0.3; //Opacity of the shading of a selected or hovered event span
+const ANALOG_POINT_RADIUS: f32 = 2.0; //Radius of analog samples drawn as points
+const SEARCH_TEXT_WIDTH: f32 = 120.0; //Width of the search bar text field
+const Y_AXIS_MIN_WIDTH: f32 = 48.0; //Minimum width of a y-axis, so that the axes of all domains line up
+const BUS_SLANT_PX: f64 = 4.0; //Width of the slanted edges of a bus value box
+const BUS_FILL_ALPHA: f32 = 0.3; //Opacity of the fill of a bus value box
+const LOGIC_UNKNOWN_FILL_ALPHA: f32 = 0.4; //Opacity of the fill of an unknown logic state band
+const BUS_CHAR_WIDTH_PX: f64 = 8.0; //Approximate width of a bus value label character, longer labels are hidden
 
 #[derive(Default)]
 pub struct WaveformSpaceView;
@@ -190,6 +228,26 @@ impl SpaceViewClass for WaveformSpaceView {
         layout.add(egui::Key::Enter);
         layout.add(" to toggle selected mode once waveforms have been selected.\n");
 
+        layout.add("Drag a domain's side panel, or ");
+        layout.add(egui::Modifiers {
+            alt: true,
+            ..Default::default()
+        });
+        layout.add("+ drag its plot, to reorder domains.\n");
+
+        layout.add(egui::Key::ArrowLeft);
+        layout.add(" / ");
+        layout.add(egui::Key::ArrowRight);
+        layout.add(" to step the timeline cursor through the samples and transitions of the selected waveforms, or all of them, and ");
+        layout.add(egui::Modifiers::COMMAND);
+        layout.add("+ ");
+        layout.add(egui::Key::ArrowLeft);
+        layout.add(" / ");
+        layout.add(egui::Key::ArrowRight);
+        layout.add(" through their events.\n");
+
+        layout.add("Use the search bar to move the timeline cursor to the previous or next edge, state, threshold crossing or event, of the selected waveforms if any.\n");
+
         layout.layout_job.into()
     }
 
@@ -208,6 +266,115 @@ impl SpaceViewClass for WaveformSpaceView {
         Box::<WaveformSpaceViewState>::default()
     }
 
+    fn selection_ui(
+        &self,
+        ctx: &re_viewer_context::ViewerContext<'_>,
+        ui: &mut egui::Ui,
+        state: &mut dyn SpaceViewState,
+        space_origin: &re_log_types::EntityPath,
+        space_view_id: re_viewer_context::SpaceViewId,
+    ) -> Result<(), re_viewer_context::SpaceViewSystemExecutionError> {
+        let state = state.downcast_mut::<WaveformSpaceViewState>()?;
+
+        let grouping_property = DomainGrouping::property(ctx, space_view_id);
+        let grouping = DomainGrouping::load(&grouping_property, space_origin)?;
+        let mut depth = grouping.depth;
+        let mut lane_rules = grouping.lane_rules.join("\n");
+
+        ui.label(RichText::new("Grouping").strong());
+        ui.horizontal(|ui| {
+            ui.label("Depth");
+            ui.add(egui::DragValue::new(&mut depth).range(1..=16))
+                .on_hover_text("Number of leading entity path parts that name a domain");
+        });
+        ui.label("Lanes").on_hover_text(
+            "One lane per line as `name = filter`, with filter rules separated by commas, \
+            e.g. `Clocks = + /A/clk, + /B/**/clk`.\n\
+            Entities go to the first lane that matches them, otherwise they are grouped by depth.",
+        );
+        // The rules are edited in memory and only written to the blueprint once the editor loses
+        // focus, not on every keystroke
+        let lane_rules_id = ui.id().with(("lane_rules", space_view_id));
+        let mut lane_rules = ui
+            .data_mut(|data| data.get_temp::<String>(lane_rules_id))
+            .unwrap_or(lane_rules);
+        let response = ui.add(
+            egui::TextEdit::multiline(&mut lane_rules)
+                .code_editor()
+                .desired_rows(3)
+                .hint_text("name = + /path/**"),
+        );
+        let committed = response.lost_focus();
+        if response.has_focus() {
+            ui.data_mut(|data| data.insert_temp(lane_rules_id, lane_rules.clone()));
+        } else {
+            ui.data_mut(|data| data.remove::<String>(lane_rules_id));
+        }
+
+        let lane_rules: Vec<String> = if !committed {
+            grouping.lane_rules.clone()
+        } else if lane_rules.is_empty() {
+            vec![]
+        } else {
+            lane_rules.split('\n').map(str::to_owned).collect()
+        };
+        grouping.save(depth, &lane_rules, ctx, &grouping_property);
+
+        let view_property = PersistedViewState::property(ctx, space_view_id);
+        let loaded_view_state = PersistedViewState::load(&view_property)?;
+        let mut view_state = loaded_view_state.clone();
+
+        ui.label(RichText::new("Display").strong());
+        ui.horizontal(|ui| {
+            ui.label("Bus radix");
+            egui::ComboBox::from_id_source(("bus_radix", space_view_id))
+                .selected_text(view_state.bus_radix.name())
+                .show_ui(ui, |ui| {
+                    for radix in Radix::ALL {
+                        ui.selectable_value(&mut view_state.bus_radix, radix, radix.name());
+                    }
+                });
+        });
+
+        ui.label("Y-axis").on_hover_text(
+            "Domains drawn with a y-axis. Domains mixing units are drawn normalized, with an \
+            axis per unit",
+        );
+        for domain in &view_state.domain_order {
+            let mut shown = view_state.y_axis_domains.contains(domain);
+            if ui.checkbox(&mut shown, domain.as_str()).changed() {
+                if shown {
+                    view_state.y_axis_domains.insert(domain.clone());
+                } else {
+                    view_state.y_axis_domains.remove(domain);
+                }
+            }
+        }
+        view_state.save(&loaded_view_state, ctx, &view_property);
+
+        if state.warnings.is_empty() {
+            return Ok(());
+        }
+
+        ui.label(RichText::new("Warnings").strong());
+        for (entity_path, entity_warnings) in &state.warnings {
+            ui.label(RichText::new(entity_path.to_string()).color(ui.visuals().warn_fg_color));
+            for warning in entity_warnings {
+                ui.label(format!("  {warning}"));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn supports_visible_time_range(&self) -> bool {
+        true
+    }
+
+    fn default_query_range(&self) -> re_viewer_context::QueryRange {
+        re_viewer_context::QueryRange::TimeRange(re_types::datatypes::TimeRange::EVERYTHING)
+    }
+
     fn layout_priority(&self) -> re_viewer_context::SpaceViewClassLayoutPriority {
         re_viewer_context::SpaceViewClassLayoutPriority::High
     }
@@ -228,12 +395,17 @@ impl SpaceViewClass for WaveformSpaceView {
         system_output: re_viewer_context::SystemExecutionOutput,
     ) -> Result<(), re_viewer_context::SpaceViewSystemExecutionError> {
         let WaveformSpaceViewState {
-            last_frame_sample_count,
-            second_marker,
-            domain_index,
-            selected_mode,
+            last_frame_time_extent,
+            reset_pending,
+            warnings,
+            layout_drag,
+            search,
         } = state.downcast_mut::<WaveformSpaceViewState>()?;
 
+        let view_property = PersistedViewState::property(ctx, query.space_view_id);
+        let loaded_view_state = PersistedViewState::load(&view_property)?;
+        let mut view_state = loaded_view_state.clone();
+
         //Global inputs
 
         let (mut current_time, time_type, timeline) = {
@@ -253,6 +425,7 @@ impl SpaceViewClass for WaveformSpaceView {
         let ctrl_pressed = ui.ctx().input(|i| i.modifiers.ctrl);
         let shift_pressed = ui.ctx().input(|i| i.modifiers.shift);
         let space_pressed = ui.ctx().input(|i| i.key_pressed(egui::Key::Space));
+        let alt_pressed = ui.ctx().input(|i| i.modifiers.alt);
 
         // Global effects from inputs
 
@@ -274,7 +447,7 @@ impl SpaceViewClass for WaveformSpaceView {
 
         //Toggle selected mode on ctrl+ space
         if ctrl_pressed && space_pressed {
-            selected_mode.toggle(&selected_entity_paths);
+            view_state.selected_mode.toggle(&selected_entity_paths);
         }
 
         //Change time and second_marker on shift+click
@@ -291,25 +464,23 @@ impl SpaceViewClass for WaveformSpaceView {
         let WaveformSystem {
             all_series,
             all_events,
+            time_extent,
+            diagnostics,
+            search_match,
         } = system_output.view_systems.get::<WaveformSystem>()?;
 
-        let min_time = all_series
-            .iter()
-            .flat_map(|(_, series)| series.iter().map(|s| s.min_time))
-            .min()
-            .unwrap_or(0);
+        // The visualizer ran the search posted last frame
+        if let Some(time) = search_match {
+            current_time = move_time_cursor(ctx, *time);
+        }
 
-        let max_time = all_series
+        *warnings = diagnostics
             .iter()
-            .flat_map(|(_, series)| series.iter().map(|s| s.max_time))
-            .max()
-            .unwrap_or(min_time);
+            .map(|(entity_path, d)| (entity_path.clone(), d.warnings()))
+            .collect();
 
-        let current_sample_count: usize = all_series
-            .iter()
-            .flat_map(|(_, series)| series.iter().map(|s| s.len_series()))
-            .sum::<usize>()
-            + all_events.len();
+        // Only the visible window is queried, the plot extent comes from all data on the timeline
+        let (min_time, max_time) = time_extent.unwrap_or((0, 0));
 
         // …then use that as an offset to avoid nasty precision issues with
         // large times (nanos since epoch does not fit into a f64).
@@ -324,10 +495,7 @@ impl SpaceViewClass for WaveformSpaceView {
         let time_zone_for_timestamps = ctx.app_options.time_zone;
 
         // Ensure all entries have an index in domain_index or get a new index (len)
-        for (domain, _) in all_series.iter() {
-            let latest_len = domain_index.len();
-            domain_index.entry(domain.clone()).or_insert(latest_len);
-        }
+        let domain_index = view_state.domain_index(all_series.keys());
 
         // Convert all_series to vec
         let mut all_series: Vec<_> = all_series
@@ -336,7 +504,7 @@ impl SpaceViewClass for WaveformSpaceView {
                 (
                     d,
                     s.into_iter()
-                        .filter(|s| selected_mode.filter_path(&s.entity_path))
+                        .filter(|s| view_state.selected_mode.filter_path(&s.entity_path))
                         .collect::<Vec<_>>(),
                 )
             })
@@ -347,17 +515,134 @@ impl SpaceViewClass for WaveformSpaceView {
 
         all_series.sort_by(|(a, _), (b, _)| domain_index[a].cmp(&domain_index[b]));
 
-        let has_new_samples = current_sample_count != *last_frame_sample_count;
+        // Y-axes of the domains showing them. Every plot reserves as much space for y-axes as the
+        // domain with the most, so that times line up across domains
+        let domain_axes = all_series
+            .iter()
+            .map(|(domain, domain_series)| {
+                if view_state.y_axis_domains.contains(domain) {
+                    UnitAxis::of_domain(domain_series.iter().map(|series| series.as_ref()))
+                } else {
+                    vec![]
+                }
+            })
+            .collect_vec();
+        let any_y_axis = domain_axes.iter().any(|axes| !axes.is_empty());
+        let right_y_axes = domain_axes
+            .iter()
+            .map(|axes| axes.len().saturating_sub(1))
+            .max()
+            .unwrap_or(0);
+
+        let has_new_samples = *time_extent != *last_frame_time_extent;
+        let reset_bounds = reset_click || has_new_samples || std::mem::take(reset_pending);
+
+        let window_id = crate::visible_window_id(query.space_view_id, &timeline_name);
+        let queried_window = ui
+            .ctx()
+            .data(|d| d.get_temp::<(WaveformTime, WaveformTime)>(window_id));
+        let mut visible_window = queried_window;
+
+        // Search bar moving the cursor to the previous or next match, in the selected entities if any
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_source(("search_kind", query.space_view_id))
+                .selected_text(search.kind.name())
+                .show_ui(ui, |ui| {
+                    for kind in SearchKind::ALL {
+                        ui.selectable_value(&mut search.kind, kind, kind.name());
+                    }
+                });
+
+            let mut step = None;
+            if let Some(hint) = search.kind.hint() {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut search.text)
+                        .hint_text(hint)
+                        .desired_width(SEARCH_TEXT_WIDTH),
+                );
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    step = Some(true);
+                }
+            }
+
+            let condition = search.condition();
+            for (forward, text, hover_text) in
+                [(false, "⏴", "Previous match"), (true, "⏵", "Next match")]
+            {
+                if ui
+                    .add_enabled(condition.is_some(), egui::Button::new(text))
+                    .on_hover_text(hover_text)
+                    .clicked()
+                {
+                    step = Some(forward);
+                }
+            }
+
+            if let (Some(forward), Some(_)) = (step, condition) {
+                step_cursor(
+                    ui.ctx(),
+                    query.space_view_id,
+                    &timeline_name,
+                    SearchRequest::new(
+                        search.clone(),
+                        selected_entity_paths.clone(),
+                        view_state.bus_radix,
+                        current_time,
+                        forward,
+                    ),
+                );
+            }
+        });
+
+        // Step the cursor through the samples and transitions with the arrow keys, or through the
+        // events with ctrl held, of the selected entities if any
+        if ui.ui_contains_pointer() && !ui.ctx().wants_keyboard_input() {
+            for (key, forward) in [(egui::Key::ArrowLeft, false), (egui::Key::ArrowRight, true)] {
+                let kind = ui.input_mut(|i| {
+                    if i.consume_key(egui::Modifiers::COMMAND, key) {
+                        Some(SearchKind::Event)
+                    } else if i.consume_key(egui::Modifiers::NONE, key) {
+                        Some(SearchKind::Sample)
+                    } else {
+                        None
+                    }
+                });
+
+                if let Some(kind) = kind {
+                    step_cursor(
+                        ui.ctx(),
+                        query.space_view_id,
+                        &timeline_name,
+                        SearchRequest::new(
+                            SearchQuery {
+                                kind,
+                                text: String::new(),
+                            },
+                            selected_entity_paths.clone(),
+                            view_state.bus_radix,
+                            current_time,
+                            forward,
+                        ),
+                    );
+                }
+            }
+        }
 
         let mut lookup_cursor_value = None;
 
         let pixels_per_point = ui.ctx().pixels_per_point();
         let axis_height = X_AXIS_FONT_SIZE_PX / pixels_per_point;
-        let plot_height = (ui.available_height() - (2.0 * axis_height)) / (all_series.len() as f32);
+        let lanes_height = ui.available_height() - (2.0 * axis_height);
+
+        let mut layout = layout_drag
+            .clone()
+            .unwrap_or_else(|| view_state.layout.clone());
+        let mut layout_dragged = false;
+        let mut layout_drag_stopped = false;
 
-        let plot_width = ui.available_width();
-        let side_panel_width = plot_width * DEFAULT_SIDE_PANEL_WIDTH_PC;
-        let plot_width = plot_width - side_panel_width;
+        let view_width = ui.available_width();
+        let side_panel_width = view_width * layout.side_panel_fraction;
+        let plot_width = view_width - side_panel_width;
 
         let axis_group_id = egui::Id::new(("axis_group", query.space_view_id, &timeline_name));
 
@@ -367,21 +652,155 @@ impl SpaceViewClass for WaveformSpaceView {
             ui.label("No data available");
             return Ok(());
         }
-        let last_time_index = all_series.len() - 1;
+
+        // Split the height between domains by their relative heights
+        let total_weight: f32 = all_series
+            .iter()
+            .map(|(domain, _)| layout.domain_height(domain))
+            .sum();
+        let height_per_weight = lanes_height / total_weight;
+        let lane_heights = all_series
+            .iter()
+            .map(|(domain, _)| {
+                (layout.domain_height(domain) * height_per_weight).max(MIN_LANE_HEIGHT)
+            })
+            .collect_vec();
 
         let min_x = (min_time - time_offset) as f64;
         let max_x = (max_time - time_offset) as f64;
         let current_time_copy = current_time;
-        let second_marker_plot_copy = *second_marker;
+        let second_marker_plot_copy = view_state.second_marker.map(|m| (m - time_offset) as f64);
+        let bus_radix = view_state.bus_radix;
+        let bus_text_color = ui.visuals().strong_text_color();
+
+        // Frequencies and edges are measured over the marker window, or else over the window
+        // shown last frame
+        let analysis_window = view_state
+            .second_marker
+            .zip(current_time)
+            .or(queried_window);
+        // The time axes are fixed rows above and below the scrolled domains, linked to their plots
+        let time_axis = |ui: &mut egui::Ui, placement: egui_plot::VPlacement| {
+            let mut plot = egui_plot::Plot::new((
+                "time_axis",
+                query.space_view_id,
+                &timeline_name,
+                placement == egui_plot::VPlacement::Top,
+            ))
+            .width(plot_width)
+            .height(axis_height)
+            .min_size([0.0, 0.0].into())
+            .show_background(false)
+            .show_grid(false)
+            .allow_double_click_reset(false)
+            .allow_scroll([true, false])
+            .allow_zoom([true, false])
+            .allow_drag([true, false])
+            .link_axis(axis_group_id, true, false)
+            .show_axes([true, any_y_axis])
+            .x_axis_position(placement)
+            .x_axis_formatter(move |time, _| {
+                format_time(
+                    time_type,
+                    (time.value as i64).saturating_add(time_offset),
+                    time_zone_for_timestamps,
+                )
+            });
+
+            // Blank y axes as wide as those of the domains, so that the time ranges line up
+            if any_y_axis {
+                plot = plot.custom_y_axes(y_axis_hints(&[], right_y_axes));
+            }
+
+            if timeline.typ() == TimeType::Time {
+                let canvas_size = egui::vec2(plot_width, axis_height);
+                plot = plot.x_grid_spacer(move |spacer| ns_grid_spacer(canvas_size, &spacer));
+            }
+
+            plot.show(ui, |plot_ui| {
+                if reset_bounds {
+                    plot_ui.set_plot_bounds(egui_plot::PlotBounds::from_min_max(
+                        [min_x, 0.0],
+                        [max_x, 1.0],
+                    ));
+                }
+            })
+            .transform
+        };
+
+        ui.horizontal_top(|ui| {
+            ui.spacing_mut().item_spacing = [0.0, 0.0].into();
+
+            // Axes are linked, the top axis' bounds are the visible window
+            let bounds = time_axis(ui, egui_plot::VPlacement::Top).bounds();
+            visible_window = Some((
+                (bounds.min()[0] as i64).saturating_add(time_offset),
+                (bounds.max()[0] as i64).saturating_add(time_offset),
+            ));
+
+            ui.with_layout(Layout::top_down(egui::Align::Center), |ui| {
+                ui.set_width(side_panel_width);
+                ui.set_height(axis_height);
+                if view_state.selected_mode.selected() {
+                    ui.label(RichText::new("SELECTED MODE").strong());
+                } else if timeline_click_mode {
+                    ui.label(RichText::new("TIME CURSOR MODE").strong());
+                }
+            });
+        });
+
+        // Plot rows and side panel frames of each domain, to drop dragged domains on
+        let mut domain_rects = vec![];
+
+        egui::ScrollArea::vertical()
+            .id_source(("lanes", query.space_view_id))
+            .max_height(lanes_height)
+            .auto_shrink([false, false])
+            .drag_to_scroll(false)
+            .show_viewport(ui, |ui, viewport| {
+        // Only the domains within the viewport are laid out and drawn
+        let mut row_top = 0.0;
+        let visible = lane_heights
+            .iter()
+            .map(|height| {
+                let top = row_top;
+                row_top += height;
+                top < viewport.max.y && top + height > viewport.min.y
+            })
+            .collect_vec();
+
+        // Frequencies of the series in view, measured again once their rows or the window change
+        let periodic: HashMap<String, PeriodicMeasurements> = analysis_window
+            .map(|(start, end)| {
+                ctx.cache.entry(|c: &mut MeasurementCache| {
+                    all_series
+                        .iter()
+                        .zip(&visible)
+                        .filter(|(_, visible)| **visible)
+                        .flat_map(|((_, domain_series), _)| domain_series.iter())
+                        .filter(|series| series.covers(start) && series.covers(end))
+                        .filter_map(|series| {
+                            let key = SeriesKey {
+                                space_view_id: query.space_view_id,
+                                entity_path: series.entity_path.clone(),
+                                timeline,
+                            };
+                            Some((series.name(), c.periodic(key, series, start, end)?))
+                        })
+                        .collect()
+                })
+            })
+            .unwrap_or_default();
 
-        ui.horizontal_centered(|ui| {
+        ui.horizontal_top(|ui| {
             ui.style_mut().spacing.item_spacing = [0.0, 0.0].into();
             let mut plot_heights = vec![];
 
-            ui.vertical(|ui| -> Result<(), re_viewer_context::SpaceViewSystemExecutionError> {
+            let plots = ui.vertical(|ui| -> Result<(), re_viewer_context::SpaceViewSystemExecutionError> {
                         let bounds_data: Vec<_> = all_series
                             .iter()
-                            .map(|(_domain, domain_series)| {
+                            .zip(&domain_axes)
+                            .map(|((_domain, domain_series), axes)| {
                                 let min_y = domain_series.iter().fold(None, |i: Option<f64>, s| {
                                     if let Some(i) = i {
                                         s.analog_points.y_range.map(|(min, _)| i.min(min))
@@ -415,7 +834,14 @@ impl SpaceViewClass for WaveformSpaceView {
                                     }
                                 };
 
-                                let new_plot_bounds = if reset_click || has_new_samples {
+                                // Domains mixing units are drawn normalized, with an axis per unit
+                                let (min_y, max_y) = if axes.len() > 1 {
+                                    (0.0, 1.0)
+                                } else {
+                                    (min_y, max_y)
+                                };
+
+                                let new_plot_bounds = if reset_bounds {
                                     let spread_y = max_y - min_y;
                                     let delta = spread_y * DEFAULT_WAVEFORM_PADDING_PC;
 
@@ -437,18 +863,32 @@ impl SpaceViewClass for WaveformSpaceView {
                 ui.set_width(plot_width);
 
                 for (i, (domain, domain_series)) in all_series.iter().enumerate() {
+                    if !visible[i] {
+                        ui.add_space(lane_heights[i]);
+                        continue;
+                    }
+
                     let (any_analog_points, new_plot_bounds) = bounds_data[i];
-                    let height = ui
+                    let rect = ui
                         .horizontal(|ui| {
                             let plot_id = ("plot", query.space_view_id, &timeline_name, &domain);
-                            if i == 0 || i == last_time_index {
-                                ui.set_height(plot_height + axis_height);
-                            } else {
-                                ui.set_height(plot_height);
-                            }
+                            ui.set_height(lane_heights[i]);
                             ui.spacing_mut().item_spacing = [0.0, 0.0].into();
 
 
+                            // Unit of each analog series for the hover label, and the unit they share if any
+                            let series_units: HashMap<String, Option<&str>> = domain_series
+                                .iter()
+                                .map(|series| (series.name(), series.unit.as_deref()))
+                                .collect();
+                            let domain_unit = domain_series
+                                .iter()
+                                .filter(|series| !series.analog_points.points.is_empty())
+                                .map(|series| series.unit.as_deref())
+                                .all_equal_value()
+                                .ok()
+                                .flatten();
+
                             let mut plot = egui_plot::Plot::new(plot_id)
                                 .min_size([0.0, 0.0].into())
                                 //TODO: Turn this on once we figure out how to set bounds on viewport reset
@@ -456,19 +896,24 @@ impl SpaceViewClass for WaveformSpaceView {
                                 .allow_double_click_reset(false)
                                 .allow_scroll([true, false])
                                 .allow_zoom([true, true])
-                                .allow_drag([true, true])
+                                .allow_drag([!alt_pressed, !alt_pressed])
                                 .link_axis(axis_group_id, true, false)
                                 .link_cursor(cursor_group_id, true, false)
-                                .label_formatter(|_name, value| {
+                                .label_formatter(|name, value| {
+                                    let unit = series_units.get(name).copied().unwrap_or(domain_unit);
+                                    let y = domain_axes[i]
+                                        .iter()
+                                        .find(|axis| axis.unit.as_deref() == unit)
+                                        .map_or(value.y, |axis| axis.denormalize(value.y));
                                     let timezone_now = time_type.format(
                                         TimeInt::new_temporal(
                                             (value.x as i64).saturating_add(time_offset),
                                         ),
                                         time_zone_for_timestamps,
                                     );
-                                    if let Some(current_time) = current_time_copy {
+                                    let label = if let Some(current_time) = current_time_copy {
                                         let out_str = if any_analog_points {
-                                           format!("{}\n", value.y)
+                                           format!("{}\n", format_si(y, unit, 3))
                                         } else {
                                             "".to_string()
                                         };
@@ -507,6 +952,16 @@ impl SpaceViewClass for WaveformSpaceView {
                                         }
                                     } else {
                                         format!("{timezone_now}")
+                                    };
+
+                                    // Frequency and duty cycle of the hovered series
+                                    match periodic.get(name) {
+                                        Some(periodic) => format!(
+                                            "{}\n{}\n",
+                                            label.trim_end(),
+                                            measurement_summary(&periodic_rows(periodic, time_type))
+                                        ),
+                                        None => label,
                                     }
                                 });
 
@@ -514,26 +969,12 @@ impl SpaceViewClass for WaveformSpaceView {
                                 plot = plot.auto_bounds([false, true].into());
                             }
 
-                            plot = match i {
-                                i if i == 0 || i == last_time_index => plot
-                                    .show_axes([true, false])
-                                    .x_axis_formatter(move |time, _| {
-                                        format_time(
-                                            time_type,
-                                            (time.value as i64).saturating_add(time_offset),
-                                            time_zone_for_timestamps,
-                                        )
-                                    }),
-                                _ => plot.show_axes([false, false]),
-                            };
+                            // The time axis is drawn in its own rows
+                            plot = plot.show_axes([false, any_y_axis]);
 
-                            plot = match i {
-                                0 => plot.x_axis_position(egui_plot::VPlacement::Top),
-                                i if i == last_time_index => {
-                                    plot.x_axis_position(egui_plot::VPlacement::Bottom)
-                                }
-                                _ => plot,
-                            };
+                            if any_y_axis {
+                                plot = plot.custom_y_axes(y_axis_hints(&domain_axes[i], right_y_axes));
+                            }
 
                             if timeline.typ() == TimeType::Time {
                                 let canvas_size = ui.available_size();
@@ -567,23 +1008,14 @@ impl SpaceViewClass for WaveformSpaceView {
                                     current_time = if let (true, Some(pointer_x)) =
                                         (plot_ui.response().clicked(), pointer_wf_x)
                                     {
-                                        {
-                                            let mut time_ctrl_write = ctx.rec_cfg.time_ctrl.write();
-                                            let timeline = *time_ctrl_write.timeline();
-                                            time_ctrl_write.set_timeline_and_time(timeline, pointer_x);
-                                            time_ctrl_write.pause();
-                                        }
-                                        {
-                                            let time_ctrl = ctx.rec_cfg.time_ctrl.read();
-                                            time_ctrl.time_i64()
-                                        }
+                                        move_time_cursor(ctx, pointer_x)
                                     } else {
                                         current_time_copy
                                     };
 
 
                                     if plot_ui.response().secondary_clicked() {
-                                        *second_marker = pointer_pl_x;
+                                        view_state.second_marker = pointer_wf_x;
                                     }
                                 }
 
@@ -602,36 +1034,61 @@ impl SpaceViewClass for WaveformSpaceView {
                                 }
 
                                 //Plot cursor marker
-                                if let Some(x) = second_marker {
+                                if let Some(x) = view_state.second_marker.map(|m| (m - time_offset) as f64) {
                                     plot_ui.vline(
-                                        egui_plot::VLine::new(*x)
+                                        egui_plot::VLine::new(x)
                                             .color(egui::Color32::YELLOW)
                                             .style(egui_plot::LineStyle::dashed_dense()),
                                     );
                                 }
 
-                                // Plot analog points
+                                // Plot analog points, decimated to a min and max per pixel column
+                                let columns = (plot_ui.response().rect.width() * pixels_per_point).max(1.0) as usize;
+                                let window_min = (current_bounds.min()[0] as i64).saturating_add(time_offset);
+                                let window_max = (current_bounds.max()[0] as i64).saturating_add(time_offset);
+
                                 for series in domain_series.iter() {
-                                    let analog_points = series
-                                        .analog_points
-                                        .iter()
-                                        .map(|(t, a)| [(t - time_offset) as f64, a.value])
-                                        .collect_vec();
+                                    let axis = UnitAxis::find(&domain_axes[i], &series.unit);
+                                    let analog_points = ctx.cache.entry(|c: &mut AnalogPyramidCache| {
+                                        c.pyramid(
+                                            SeriesKey {
+                                                space_view_id: query.space_view_id,
+                                                entity_path: series.entity_path.clone(),
+                                                timeline,
+                                            },
+                                            series.instance,
+                                            &series.analog_points,
+                                        )
+                                            .decimate(window_min, window_max, columns)
+                                    })
+                                    .into_iter()
+                                    .map(|(t, value)| [(t - time_offset) as f64, axis.map_or(value, |axis| axis.normalize(value))])
+                                    .collect_vec();
 
                                     let highlight =  selected_entity_paths.get(&series.entity_path).is_some();
 
                                     let color = color_hover(series.color, hovered_entity_paths.get(&series.entity_path).is_some());
 
                                     let analog_line_id =
-                                        egui::Id::new(("analog", series.entity_path.hash()));
-
-                                    plot_ui.line(
-                                        egui_plot::Line::new(analog_points)
-                                            .name(series.entity_path.to_string())
-                                            .color(color)
-                                            .id(analog_line_id)
-                                            .highlight(highlight),
-                                    );
+                                        egui::Id::new(("analog", series.entity_path.hash(), series.instance));
+
+                                    match series.interpolation {
+                                        Interpolation::Points => plot_ui.points(
+                                            egui_plot::Points::new(analog_points)
+                                                .name(series.name())
+                                                .color(color)
+                                                .radius(ANALOG_POINT_RADIUS)
+                                                .id(analog_line_id)
+                                                .highlight(highlight),
+                                        ),
+                                        interpolation => plot_ui.line(
+                                            egui_plot::Line::new(step_points(analog_points, interpolation))
+                                                .name(series.name())
+                                                .color(color)
+                                                .id(analog_line_id)
+                                                .highlight(highlight),
+                                        ),
+                                    }
 
                                     plot_item_id_to_entity_path.insert(analog_line_id, series.entity_path.clone());
                                 }
@@ -665,6 +1122,7 @@ impl SpaceViewClass for WaveformSpaceView {
                                 let discrete_series_box_width =
                                     discrete_series_height_step * DISCRETE_BOX_PADDING_PC;
                                 let discrete_series_max = discrete_bounds.max()[1] - (discrete_series_height_step/2.0);
+                                let x_units_per_px = plot_ui.transform().dvalue_dpos()[0];
 
                                 for (l, series) in discrete_series_of_interest.enumerate() {
                                     let y_offset = discrete_series_max
@@ -684,8 +1142,8 @@ impl SpaceViewClass for WaveformSpaceView {
                                         .tuple_windows::<(_, _)>()
                                         .filter_map(
                                             |(
-                                                (t, DiscreteTransition { label, color: c , kind}),
-                                                (t_end, _),
+                                                (t, transition @ DiscreteTransition { label, color: c , kind}),
+                                                (t_end, next),
                                             )| {
                                                 let t = (*t - time_offset) as f64;
                                                 let t_end = (*t_end - time_offset) as f64;
@@ -706,14 +1164,11 @@ impl SpaceViewClass for WaveformSpaceView {
                                                     .name(if let Some(label) = label {
                                                         format!(
                                                             "{}:{}",
-                                                            series.entity_path.to_string(),
+                                                            series.name(),
                                                             label.as_str()
                                                         )
                                                     } else {
-                                                        format!(
-                                                            "{}",
-                                                            series.entity_path.to_string(),
-                                                        )
+                                                        series.name()
                                                     })
                                                     .fill(color)
                                                     .box_width(discrete_series_box_width)
@@ -724,7 +1179,7 @@ impl SpaceViewClass for WaveformSpaceView {
                                                 ),
                                                     crate::DiscreteTransitionKind::Line => {
 
-                                                        let id = egui::Id::new(("discrete_line", series.entity_path.hash()));
+                                                        let id = egui::Id::new(("discrete_line", series.entity_path.hash(), series.instance));
 
                                                         let points = vec![[min_x, y_offset], [max_x, y_offset]];
                                                         plot_ui.line(Line::new(points)
@@ -735,6 +1190,110 @@ impl SpaceViewClass for WaveformSpaceView {
                                                             .allow_hover(true)
                                                         );
 
+                                                        plot_item_id_to_entity_path.insert(id, series.entity_path.clone());
+                                                        None
+                                                    },
+                                                    crate::DiscreteTransitionKind::Logic(state) => {
+                                                        if t_end < current_bounds.min()[0] || t > current_bounds.max()[0] {
+                                                            return None;
+                                                        }
+
+                                                        let half_height = discrete_series_box_width / 2.0;
+                                                        let level = |state: &LogicState| match state {
+                                                            LogicState::Low => Some(y_offset - half_height),
+                                                            LogicState::High => Some(y_offset + half_height),
+                                                            LogicState::Unknown | LogicState::HighImpedance => None,
+                                                        };
+                                                        let id = egui::Id::new(("discrete_logic", series.entity_path.hash(), series.instance));
+
+                                                        match state {
+                                                            // Unknown is a band over the whole lane height
+                                                            LogicState::Unknown => {
+                                                                plot_ui.polygon(egui_plot::Polygon::new(vec![
+                                                                        [t, y_offset - half_height],
+                                                                        [t, y_offset + half_height],
+                                                                        [t_end, y_offset + half_height],
+                                                                        [t_end, y_offset - half_height],
+                                                                    ])
+                                                                    .id(id)
+                                                                    .name(format!("{}:X", series.name()))
+                                                                    .fill_color(color.gamma_multiply(LOGIC_UNKNOWN_FILL_ALPHA))
+                                                                    .stroke(egui::Stroke::new(stroke_width, color))
+                                                                    .highlight(highlight)
+                                                                );
+                                                            }
+                                                            // High impedance floats at mid level
+                                                            LogicState::HighImpedance => {
+                                                                plot_ui.line(Line::new(vec![[t, y_offset], [t_end, y_offset]])
+                                                                    .id(id)
+                                                                    .name(format!("{}:Z", series.name()))
+                                                                    .color(color)
+                                                                    .stroke(egui::Stroke::new(stroke_width, color))
+                                                                    .highlight(highlight)
+                                                                    .allow_hover(true)
+                                                                );
+                                                            }
+                                                            LogicState::Low | LogicState::High => {
+                                                                let y = level(state).unwrap_or(y_offset);
+                                                                let mut points = vec![[t, y], [t_end, y]];
+
+                                                                // Edge up or down to the next 0 or 1
+                                                                if let crate::DiscreteTransitionKind::Logic(next_state) = &next.kind {
+                                                                    if let Some(next_y) = level(next_state) {
+                                                                        points.push([t_end, next_y]);
+                                                                    }
+                                                                }
+
+                                                                plot_ui.line(Line::new(points)
+                                                                    .id(id)
+                                                                    .name(format!("{}:{}", series.name(), state.as_str()))
+                                                                    .color(color)
+                                                                    .stroke(egui::Stroke::new(stroke_width, color))
+                                                                    .highlight(highlight)
+                                                                    .allow_hover(true)
+                                                                );
+                                                            }
+                                                        }
+
+                                                        plot_item_id_to_entity_path.insert(id, series.entity_path.clone());
+                                                        None
+                                                    },
+                                                    crate::DiscreteTransitionKind::Bus { .. } => {
+                                                        if t_end < current_bounds.min()[0] || t > current_bounds.max()[0] {
+                                                            return None;
+                                                        }
+
+                                                        // Logic analyzer style value box with slanted edges at the transitions
+                                                        let half_height = discrete_series_box_width / 2.0;
+                                                        let slant = (BUS_SLANT_PX * x_units_per_px).min((t_end - t) / 2.0);
+                                                        let points = vec![
+                                                            [t, y_offset],
+                                                            [t + slant, y_offset + half_height],
+                                                            [t_end - slant, y_offset + half_height],
+                                                            [t_end, y_offset],
+                                                            [t_end - slant, y_offset - half_height],
+                                                            [t + slant, y_offset - half_height],
+                                                        ];
+
+                                                        let value = transition.display_label(bus_radix).unwrap_or_default();
+                                                        let id = egui::Id::new(("discrete_bus", series.entity_path.hash(), series.instance));
+
+                                                        plot_ui.polygon(egui_plot::Polygon::new(points)
+                                                            .id(id)
+                                                            .name(format!("{}:{}", series.name(), value))
+                                                            .fill_color(color.gamma_multiply(BUS_FILL_ALPHA))
+                                                            .stroke(egui::Stroke::new(stroke_width, color))
+                                                            .highlight(highlight)
+                                                        );
+
+                                                        // Only label boxes wide enough to fit the value
+                                                        if (t_end - t) / x_units_per_px > value.len() as f64 * BUS_CHAR_WIDTH_PX {
+                                                            plot_ui.text(egui_plot::Text::new(
+                                                                [(t + t_end) / 2.0, y_offset].into(),
+                                                                RichText::new(value).color(bus_text_color),
+                                                            ));
+                                                        }
+
                                                         plot_item_id_to_entity_path.insert(id, series.entity_path.clone());
                                                         None
                                                     },
@@ -745,7 +1304,7 @@ impl SpaceViewClass for WaveformSpaceView {
 
                                     if !box_elements.is_empty() {
                                         let highlight =  selected_entity_paths.get(&series.entity_path).is_some();
-                                        let id = egui::Id::new(("discrete_box", series.entity_path.hash()));
+                                        let id = egui::Id::new(("discrete_box", series.entity_path.hash(), series.instance));
 
                                         plot_ui.box_plot(
                                             egui_plot::BoxPlot::new(box_elements)
@@ -771,6 +1330,32 @@ impl SpaceViewClass for WaveformSpaceView {
                                         let id = egui::Id::new(("event_marker",t, i ));
                                         let x = (*t - time_offset) as f64;
 
+                                        // Spans shade the plot over their duration, and show their label while hovered
+                                        if let Some(duration) = event_marker.duration {
+                                            let x_end = x + duration as f64;
+                                            if x_end < current_bounds.min()[0] || x > current_bounds.max()[0] {
+                                                continue;
+                                            }
+
+                                            let (y_min, y_max) = (current_bounds.min()[1], current_bounds.max()[1]);
+                                            plot_ui.polygon(
+                                                egui_plot::Polygon::new(vec![[x, y_min], [x, y_max], [x_end, y_max], [x_end, y_min]])
+                                                    .id(id)
+                                                    .fill_color(event_marker.color.gamma_multiply(if highlight { SPAN_HIGHLIGHT_FILL_ALPHA } else { SPAN_FILL_ALPHA }))
+                                                    .stroke(egui::Stroke::new(1.0, event_marker.color))
+                                                    .highlight(highlight)
+                                            );
+
+                                            if let (Some(pointer_x), Some(pointer_y), Some(label)) = (pointer_pl_x, pointer_y, &event_marker.label) {
+                                                if plot_ui.response().hovered() && x <= pointer_x && pointer_x <= x_end {
+                                                    plot_ui.text(egui_plot::Text::new([pointer_x, pointer_y].into(), RichText::new(label).color(event_marker.color)).anchor(egui::Align2::LEFT_BOTTOM));
+                                                }
+                                            }
+
+                                            plot_item_id_to_entity_path.insert(id, event_marker.entity_path.clone());
+                                            continue;
+                                        }
+
                                         if let (Some(pointer_y), Some(label), true, true) = (pointer_y, &event_marker.label, highlight, plot_ui.response().hovered()) {
                                             plot_ui.text(egui_plot::Text::new([x, pointer_y].into(), RichText::new(label).color(event_marker.color)));
                                         }
@@ -790,6 +1375,11 @@ impl SpaceViewClass for WaveformSpaceView {
 
                             });
 
+                            // Alt + drag moves the domain instead of panning
+                            if alt_pressed && response.drag_started() {
+                                egui::DragAndDrop::set_payload(ui.ctx(), DraggedDomain(domain.clone()));
+                            }
+
                             if !reset_click {
                                 if let Some(hovered_entity_path) = hovered_plot_item.and_then(|item| plot_item_id_to_entity_path.get(&item)) {
                                     hovered_entity_paths.insert(hovered_entity_path.clone());
@@ -803,11 +1393,42 @@ impl SpaceViewClass for WaveformSpaceView {
 
                         })
                         .response
-                        .rect
-                        .height();
+                        .rect;
 
-                    plot_heights.push(height);
+                    plot_heights.push(rect.height());
+                    domain_rects.push((domain.clone(), rect));
                 }
+
+                // Splitters between domain rows move height from one row to the next
+                let min_weight = MIN_LANE_HEIGHT / height_per_weight;
+                for ((upper, upper_rect), (lower, _)) in domain_rects.iter().tuple_windows() {
+                    let y = upper_rect.bottom();
+                    let splitter = egui::Rect::from_x_y_ranges(
+                        upper_rect.x_range(),
+                        (y - SPLITTER_GRAB_WIDTH)..=(y + SPLITTER_GRAB_WIDTH),
+                    );
+                    let response = ui
+                        .interact(splitter, egui::Id::new(("lane_splitter", query.space_view_id, upper)), egui::Sense::drag())
+                        .on_hover_cursor(egui::CursorIcon::ResizeVertical);
+
+                    if response.dragged() {
+                        let upper_weight = layout.domain_height(upper);
+                        let lower_weight = layout.domain_height(lower);
+                        let delta = (response.drag_delta().y / height_per_weight)
+                            .max(min_weight - upper_weight)
+                            .min(lower_weight - min_weight);
+                        layout.domain_heights.insert(upper.clone(), upper_weight + delta);
+                        layout.domain_heights.insert(lower.clone(), lower_weight - delta);
+                    }
+
+                    if response.hovered() || response.dragged() {
+                        ui.painter().hline(upper_rect.x_range(), y, ui.visuals().widgets.active.fg_stroke);
+                    }
+
+                    layout_dragged |= response.dragged();
+                    layout_drag_stopped |= response.drag_stopped();
+                }
+
                 Ok(())
             });
 
@@ -824,16 +1445,16 @@ impl SpaceViewClass for WaveformSpaceView {
                 ui.set_width(side_panel_width);
                 ui.style_mut().spacing.item_spacing = [0.0, 0.0].into();
 
-                ui.with_layout(Layout::top_down(egui::Align::Center),|ui| {
-                    ui.set_height(axis_height);
-                    if selected_mode.selected(){
-                        ui.label( RichText::new("SELECTED MODE").strong());
-                    }
-                    else if timeline_click_mode {
-                        ui.label( RichText::new("TIME CURSOR MODE").strong());
+                let measure_window = view_state.second_marker.zip(current_time);
+                for (i, (domain, domain_series)) in all_series.iter().enumerate() {
+                    if !visible[i] {
+                        ui.add_space(lane_heights[i]);
+                        continue;
                     }
-                });
-                for (i, (_domain, domain_series)) in all_series.iter().enumerate() {
+
+                    let plot_height = lane_heights[i];
+                    let drag_id = egui::Id::new(("domain_drag", query.space_view_id, domain));
+                    let rect = ui.dnd_drag_source(drag_id, DraggedDomain(domain.clone()), |ui| {
                     egui::Frame::none()
                         .stroke(egui::Stroke::new(1.0, frame_stroke_color))
                         .show(ui, |ui| {
@@ -854,59 +1475,15 @@ impl SpaceViewClass for WaveformSpaceView {
                                             lookup_cursor_value.or(current_time)
                                         {
                                             for series in domain_series.iter() {
-                                                let mut is_interpolated = false;
-                                                let analog_point = series
-                                                    .analog_points
-                                                    .points
-                                                    .range((
-                                                        Bound::Included(
-                                                            &(seek_time - CURSOR_TIME_TOLERANCE),
-                                                        ),
-                                                        Bound::Included(
-                                                            &(seek_time + CURSOR_TIME_TOLERANCE),
-                                                        ),
-                                                    ))
-                                                    .next()
-                                                    .map(|p| p.1.value)
-                                                    //Lazy evaluation
-                                                    .or_else(|| {
-                                                        let point_before = series
-                                                            .analog_points
-                                                            .points
-                                                            .range((
-                                                                Bound::Unbounded,
-                                                                Bound::Excluded(&seek_time),
-                                                            ))
-                                                            .last();
-
-                                                        let point_after = series
-                                                            .analog_points
-                                                            .points
-                                                            .range((
-                                                                Bound::Excluded(&seek_time),
-                                                                Bound::Unbounded,
-                                                            ))
-                                                            .next();
-
-                                                        if let (Some((t1, p1)), Some((t2, p2))) =
-                                                            (point_before, point_after)
-                                                        {
-                                                            let t1 = *t1 as f64;
-                                                            let t2 = *t2 as f64;
-
-                                                            //Slope
-                                                            let slope =
-                                                                (p2.value - p1.value) / (t2 - t1);
+                                                // Rows outside the queried window are unknown
+                                                let covered = series.covers(seek_time);
 
-                                                            let value = p1.value
-                                                                + (slope * (seek_time as f64 - t1));
-
-                                                            is_interpolated = true;
-                                                            Some(value)
-                                                        } else {
-                                                            None
-                                                        }
-                                                    });
+                                                let (analog_point, is_interpolated) = series
+                                                    .analog_points
+                                                    .value_at(seek_time, CURSOR_TIME_TOLERANCE, series.interpolation)
+                                                    .filter(|_| covered)
+                                                    .unzip();
+                                                let is_interpolated = is_interpolated.unwrap_or(false);
 
                                                 let discrete_point = series
                                                     .discrete_points
@@ -915,7 +1492,8 @@ impl SpaceViewClass for WaveformSpaceView {
                                                         Bound::Unbounded,
                                                         Bound::Included(&seek_time),
                                                     ))
-                                                    .last();
+                                                    .last()
+                                                    .filter(|_| covered);
 
                                                 body.row(12.0, |mut row| {
                                                     let hovered = hovered_entity_paths
@@ -937,11 +1515,16 @@ impl SpaceViewClass for WaveformSpaceView {
                                                             Some(egui::TextWrapMode::Truncate);
 
                                                         let text_color = series.color ;
-                                                        responses.push(
-                                                        ui.label(
-                                                            RichText::new(series.entity_path.clone())
-                                                            .color(tcolor_hover(text_color, hovered)),
-                                                        ));
+                                                        responses.push(ui.horizontal(|ui| {
+                                                            if let Some(entity_warnings) = warnings.get(&series.entity_path) {
+                                                                ui.label(RichText::new("⚠ ").color(ui.visuals().warn_fg_color))
+                                                                    .on_hover_text(entity_warnings.join("\n"));
+                                                            }
+                                                            ui.label(
+                                                                RichText::new(series.name())
+                                                                .color(tcolor_hover(text_color, hovered)),
+                                                            )
+                                                        }).inner);
                                                     });
 
                                                     responses.push(response);
@@ -954,10 +1537,10 @@ impl SpaceViewClass for WaveformSpaceView {
                                                             //Limited to 3rd decimal place precision
                                                             labels.push(
                                                                 RichText::new(format!(
-                                                                    "{:.3} {}",
-                                                                    analog_point,
+                                                                    "{}{}",
+                                                                    format_si(analog_point, series.unit.as_deref(), 3),
                                                                     if is_interpolated {
-                                                                        " (I)"
+                                                                        interpolation_marker(series.interpolation)
                                                                     } else {
                                                                         ""
                                                                     }
@@ -967,20 +1550,16 @@ impl SpaceViewClass for WaveformSpaceView {
                                                             );
                                                         }
 
-                                                        if let Some((
-                                                            &t,
-                                                            DiscreteTransition {
-                                                                label: Some(label),
-                                                                color,
-                                                                kind: _
-                                                            },
-                                                        )) = discrete_point
+                                                        if let Some((&t, transition, label)) = discrete_point.and_then(|(t, transition)| {
+                                                            Some((t, transition, transition.display_label(bus_radix)?))
+                                                        })
                                                         {
+                                                            let color = &transition.color;
                                                             if !labels.is_empty() {
                                                                 labels.push(RichText::new(" | "));
                                                             }
                                                             labels.push(
-                                                                RichText::new(label.clone()).color(tcolor_hover(*color, hovered))
+                                                                RichText::new(label).color(tcolor_hover(*color, hovered))
                                                             );
 
                                                             labels.push(
@@ -1012,35 +1591,372 @@ impl SpaceViewClass for WaveformSpaceView {
                                                     ctx.select_hovered_on_click(&response, re_viewer_context::Item::DataResult(query.space_view_id,series.entity_path.clone().into()));
 
                                                 });
+
+                                                // Measurements in between the cursor and the second marker
+                                                if let Some((start, end)) = measure_window
+                                                    .filter(|(start, end)| series.covers(*start) && series.covers(*end))
+                                                {
+                                                    let analog = AnalogMeasurements::measure(&series.analog_points, series.interpolation, start, end);
+                                                    let mut counts = DiscreteCounts::count(&series.discrete_points, all_events, &series.entity_path, bus_radix, start, end);
+                                                    // Events are counted once per entity, on its first channel
+                                                    if series.instance.unwrap_or(0) != 0 {
+                                                        counts.events.clear();
+                                                    }
+
+                                                    let rows = measurement_rows(analog.as_ref(), &counts, series.unit.as_deref(), time_type);
+                                                    measurement_row(&mut body, "M..T", &rows);
+                                                }
+
+                                                if let Some(periodic) = periodic.get(&series.name()) {
+                                                    measurement_row(&mut body, "Freq", &periodic_rows(periodic, time_type));
+                                                }
+
+                                                // Analog edge nearest to the cursor
+                                                if let Some(edge) = analysis_window
+                                                    .filter(|(start, end)| series.covers(*start) && series.covers(*end))
+                                                    .and_then(|(start, end)| {
+                                                        let key = SeriesKey {
+                                                            space_view_id: query.space_view_id,
+                                                            entity_path: series.entity_path.clone(),
+                                                            timeline,
+                                                        };
+                                                        ctx.cache.entry(|c: &mut MeasurementCache| {
+                                                            c.nearest_edge(key, series, start, end, seek_time)
+                                                        })
+                                                    })
+                                                {
+                                                    measurement_row(&mut body, "Edge", &edge_rows(&edge, time_type));
+                                                }
                                             }
                                         }
                                     });
                             });
                         });
+                    }).response.rect;
+                    domain_rects.push((domain.clone(), rect));
                 }
-                ui.with_layout(Layout::top_down(egui::Align::Center),|ui| {
-                    ui.set_height(axis_height);
+            });
+
+            // Splitter between the plots and the side panel
+            let plots_rect = plots.response.rect;
+            let x = plots_rect.right();
+            let splitter = egui::Rect::from_x_y_ranges(
+                (x - SPLITTER_GRAB_WIDTH)..=(x + SPLITTER_GRAB_WIDTH),
+                plots_rect.y_range(),
+            );
+            let response = ui
+                .interact(splitter, egui::Id::new(("side_panel_splitter", query.space_view_id)), egui::Sense::drag())
+                .on_hover_cursor(egui::CursorIcon::ResizeHorizontal);
+
+            if response.dragged() {
+                layout.side_panel_fraction = (layout.side_panel_fraction
+                    - response.drag_delta().x / view_width)
+                    .clamp(MIN_SIDE_PANEL_WIDTH_PC, MAX_SIDE_PANEL_WIDTH_PC);
+            }
+
+            if response.hovered() || response.dragged() {
+                ui.painter().vline(x, plots_rect.y_range(), ui.visuals().widgets.active.fg_stroke);
+            }
 
-                    if let Some(second_marker_delta) = second_marker_plot_copy.map(|m| (m as i64).checked_add(time_offset)).flatten().map(|m| current_time.map(|c| c.checked_sub(m)).flatten()).flatten() {
+            layout_dragged |= response.dragged();
+            layout_drag_stopped |= response.drag_stopped();
+        });
+            });
+
+        ui.horizontal_top(|ui| {
+            ui.spacing_mut().item_spacing = [0.0, 0.0].into();
+            time_axis(ui, egui_plot::VPlacement::Bottom);
+
+            ui.with_layout(Layout::top_down(egui::Align::Center), |ui| {
+                ui.set_width(side_panel_width);
+                ui.set_height(axis_height);
+                if let Some(second_marker_delta) = view_state
+                    .second_marker
+                    .zip(current_time)
+                    .and_then(|(m, c)| c.checked_sub(m))
+                {
                     ui.label(format!(
                         "T-M: {}",
-                        format_time(
-                            time_type,
-                            second_marker_delta,
-                            time_zone_for_timestamps
-                        )
+                        format_time(time_type, second_marker_delta, time_zone_for_timestamps)
                     ));
-}
-                });
+                }
             });
         });
 
-        *last_frame_sample_count = current_sample_count;
+        // Keep dragged sizes in the view state until the drag stops, then store them
+        if layout_drag_stopped {
+            view_state.layout = layout;
+            *layout_drag = None;
+        } else if layout_dragged {
+            *layout_drag = Some(layout);
+        }
+
+        // Move a dragged domain before or after the domain it is dropped on
+        if egui::DragAndDrop::has_payload_of_type::<DraggedDomain>(ui.ctx()) {
+            let drop_target = ui.ctx().pointer_hover_pos().and_then(|pointer| {
+                domain_rects
+                    .iter()
+                    .find(|(_, rect)| rect.contains(pointer))
+                    .map(|(domain, rect)| (domain, *rect, pointer.y > rect.center().y))
+            });
+
+            if let Some((target, rect, after)) = drop_target {
+                let y = if after { rect.bottom() } else { rect.top() };
+                ui.painter()
+                    .hline(rect.x_range(), y, ui.visuals().selection.stroke);
+
+                if ui.input(|i| i.pointer.any_released()) {
+                    if let Some(dragged) =
+                        egui::DragAndDrop::take_payload::<DraggedDomain>(ui.ctx())
+                    {
+                        view_state.move_domain(&dragged.0, target, after);
+                    }
+                }
+            }
+        }
+
+        // Query the new window next frame
+        if visible_window != queried_window {
+            if let Some(window) = visible_window {
+                ui.ctx().data_mut(|d| d.insert_temp(window_id, window));
+            }
+            ui.ctx().request_repaint();
+            *reset_pending = reset_bounds;
+        }
+
+        *last_frame_time_extent = *time_extent;
+
+        view_state.save(&loaded_view_state, ctx, &view_property);
 
         Ok(())
     }
 }
 
+/// Posts `request` for the visualizer to run over the store next frame, which then moves the
+/// cursor to the match
+fn step_cursor(
+    egui_ctx: &egui::Context,
+    space_view_id: re_viewer_context::SpaceViewId,
+    timeline_name: &str,
+    request: SearchRequest,
+) {
+    let id = crate::search_request_id(space_view_id, timeline_name);
+    egui_ctx.data_mut(|d| d.insert_temp(id, request));
+    egui_ctx.request_repaint();
+}
+
+/// Moves the timeline cursor to `time` and pauses, returning the time the cursor is at
+fn move_time_cursor(
+    ctx: &re_viewer_context::ViewerContext<'_>,
+    time: WaveformTime,
+) -> Option<WaveformTime> {
+    {
+        let mut time_ctrl_write = ctx.rec_cfg.time_ctrl.write();
+        let timeline = *time_ctrl_write.timeline();
+        time_ctrl_write.set_timeline_and_time(timeline, time);
+        time_ctrl_write.pause();
+    }
+    let time_ctrl = ctx.rec_cfg.time_ctrl.read();
+    time_ctrl.time_i64()
+}
+
+/// Marker after a side panel value in between samples
+fn interpolation_marker(interpolation: Interpolation) -> &'static str {
+    match interpolation {
+        Interpolation::Linear => " (I)",
+        Interpolation::PreviousHold => " (H)",
+        Interpolation::Nearest => " (N)",
+        Interpolation::Points => "",
+    }
+}
+
+/// Side panel row summarizing `rows`, which are all listed on hover
+fn measurement_row(
+    body: &mut egui_extras::TableBody<'_>,
+    name: &str,
+    rows: &[(&'static str, String)],
+) {
+    if rows.is_empty() {
+        return;
+    }
+
+    body.row(12.0, |mut row| {
+        row.col(|ui| {
+            ui.label(RichText::new(name).weak());
+        });
+        row.col(|ui| {
+            ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Truncate);
+            ui.label(RichText::new(measurement_summary(rows)).weak())
+                .on_hover_ui(|ui| {
+                    egui::Grid::new("measurements")
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            for (name, value) in rows {
+                                ui.label(*name);
+                                ui.label(value);
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+    });
+}
+
+/// The first two of `rows`, on a single line
+fn measurement_summary(rows: &[(&'static str, String)]) -> String {
+    rows.iter()
+        .take(2)
+        .map(|(name, value)| format!("{name} {value}"))
+        .join(", ")
+}
+
+/// Names and formatted values of the frequency, period, pulse width and duty cycle of a series
+fn periodic_rows(
+    periodic: &PeriodicMeasurements,
+    time_type: TimeType,
+) -> Vec<(&'static str, String)> {
+    let frequency = match seconds_per_time_unit(time_type) {
+        Some(seconds) => format_si(periodic.frequency() / seconds, Some("Hz"), 3),
+        None => format_si(periodic.frequency(), Some("/step"), 3),
+    };
+    let period = format_duration(periodic.period, time_type);
+    let pulse_width = periodic
+        .pulse_width
+        .map(|width| format_duration(width, time_type));
+
+    let mut rows = vec![("Freq", frequency)];
+    if let Some(duty_cycle) = periodic.duty_cycle() {
+        rows.push(("Duty", format!("{:.1} %", duty_cycle * 100.0)));
+    }
+    rows.push(("Period", period));
+    rows.extend(pulse_width.map(|width| ("Pulse width", width)));
+    rows
+}
+
+/// Names and formatted values of the transition time, overshoots and settling time of an edge
+fn edge_rows(edge: &EdgeMeasurements, time_type: TimeType) -> Vec<(&'static str, String)> {
+    let percent = |fraction: f64| format!("{:.1} %", fraction * 100.0);
+    let duration =
+        |time: Option<f64>| time.map_or("-".to_owned(), |t| format_duration(t, time_type));
+
+    vec![
+        (
+            if edge.rising { "Rise" } else { "Fall" },
+            duration(edge.transition_time),
+        ),
+        ("Overshoot", percent(edge.overshoot)),
+        ("Undershoot", percent(edge.undershoot)),
+        ("Settling", duration(edge.settling_time)),
+    ]
+}
+
+/// Formats a duration in time units of the timeline, in seconds on time timelines and in steps on
+/// sequences
+fn format_duration(time: f64, time_type: TimeType) -> String {
+    match seconds_per_time_unit(time_type) {
+        Some(seconds) => format_si(time * seconds, Some("s"), 3),
+        None => format!("{time:.3} steps"),
+    }
+}
+
+/// Names and formatted values of the measurements of a series in between the markers
+fn measurement_rows(
+    analog: Option<&AnalogMeasurements>,
+    counts: &DiscreteCounts,
+    unit: Option<&str>,
+    time_type: TimeType,
+) -> Vec<(&'static str, String)> {
+    let mut rows = vec![];
+
+    if let Some(analog) = analog {
+        // Slope and area are per second on time timelines, per step on sequences
+        let seconds = seconds_per_time_unit(time_type);
+        let (time_scale, time_unit) = seconds.map_or((1.0, "step"), |s| (s, "s"));
+        let slope_unit = format!("{}/{time_unit}", unit.unwrap_or_default());
+        let area_unit = unit.map_or(time_unit.to_owned(), |unit| format!("{unit}·{time_unit}"));
+
+        rows.extend([
+            ("P-P", format_si(analog.peak_to_peak(), unit, 3)),
+            ("Mean", format_si(analog.mean, unit, 3)),
+            ("Min", format_si(analog.min, unit, 3)),
+            ("Max", format_si(analog.max, unit, 3)),
+            ("RMS", format_si(analog.rms, unit, 3)),
+            ("ΔY", format_si(analog.delta_y, unit, 3)),
+        ]);
+        if let Some(slope) = analog.slope {
+            rows.push(("Slope", format_si(slope / time_scale, Some(&slope_unit), 3)));
+        }
+        if let Some(area) = analog.area {
+            rows.push(("Area", format_si(area * time_scale, Some(&area_unit), 3)));
+        }
+    }
+
+    let per_label = |total: usize, counts: &BTreeMap<String, usize>| {
+        let labels = counts
+            .iter()
+            .map(|(label, n)| format!("{label} ×{n}"))
+            .join(", ");
+        format!("{total} ({labels})")
+    };
+    if !counts.transitions.is_empty() {
+        rows.push((
+            "Transitions",
+            per_label(counts.total_transitions(), &counts.transitions),
+        ));
+    }
+    if !counts.events.is_empty() {
+        rows.push(("Events", per_label(counts.total_events(), &counts.events)));
+    }
+
+    rows
+}
+
+/// Adds the corners of the steps between `points` for hold and nearest interpolation
+fn step_points(points: Vec<[f64; 2]>, interpolation: Interpolation) -> Vec<[f64; 2]> {
+    let step_at = |[x1, _]: [f64; 2], [x2, _]: [f64; 2]| match interpolation {
+        Interpolation::PreviousHold => Some(x2),
+        Interpolation::Nearest => Some((x1 + x2) / 2.0),
+        Interpolation::Linear | Interpolation::Points => None,
+    };
+
+    if points.len() < 2 || step_at(points[0], points[1]).is_none() {
+        return points;
+    }
+
+    let mut stepped = Vec::with_capacity(points.len() * 3);
+    for (&p1, &p2) in points.iter().tuple_windows() {
+        stepped.push(p1);
+        if let Some(x) = step_at(p1, p2) {
+            stepped.push([x, p1[1]]);
+            stepped.push([x, p2[1]]);
+        }
+    }
+    stepped.extend(points.last());
+    stepped
+}
+
+/// Y-axes of a domain: its first axis on the left, the others on the right, padded with empty
+/// axes up to `right_axes` on the right
+fn y_axis_hints(axes: &[UnitAxis], right_axes: usize) -> Vec<egui_plot::AxisHints<'static>> {
+    (0..=right_axes)
+        .map(|index| {
+            let axis = axes.get(index).cloned();
+            egui_plot::AxisHints::new_y()
+                .placement(if index == 0 {
+                    egui_plot::HPlacement::Left
+                } else {
+                    egui_plot::HPlacement::Right
+                })
+                .min_thickness(Y_AXIS_MIN_WIDTH)
+                .formatter(move |mark, range| {
+                    axis.as_ref().map_or_else(String::new, |axis| {
+                        axis.format_tick(mark.value, mark.step_size, range)
+                    })
+                })
+        })
+        .collect()
+}
+
 fn format_time(time_type: TimeType, time_int: i64, time_zone_for_timestamps: TimeZone) -> String {
     if time_type == TimeType::Time {
         let time = re_log_types::Time::from_ns_since_epoch(time_int);
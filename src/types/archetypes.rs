@@ -2,10 +2,24 @@ use re_types::ComponentName;
 #[derive(Clone, Debug, PartialEq)]
 pub enum WaveformPoint {
     Scalar(super::components::Scalar),
+    /// One scalar per sub-channel `entity[i]`
+    Scalars(Vec<super::components::Scalar>),
     DiscreteState(super::components::DiscreteState),
+    /// One discrete state per sub-channel `entity[i]`
+    DiscreteStates(Vec<super::components::DiscreteState>),
     DiscreteStateInit(super::components::DiscreteStateInit),
     DiscreteStateNormal(super::components::DiscreteStateNormal),
     Event(super::components::Event),
+    /// Value of a multi-bit bus
+    BusValue(super::components::BusValue),
+    /// Four-state logic bit
+    LogicBit(super::components::LogicBit),
+    /// Event with a duration
+    EventSpan(super::components::EventSpan),
+    /// Discrete state given by its name
+    TextState(super::components::TextState),
+    /// Uniformly sampled scalars, starting at the row's time
+    SampleBlock(super::components::SampleBlock),
 }
 impl WaveformPoint {
     #[inline]
@@ -13,11 +27,25 @@ impl WaveformPoint {
         WaveformPoint::Scalar(scalar.into())
     }
 
+    #[inline]
+    pub fn new_scalars(
+        scalars: impl IntoIterator<Item = impl Into<super::components::Scalar>>,
+    ) -> Self {
+        WaveformPoint::Scalars(scalars.into_iter().map(Into::into).collect())
+    }
+
     #[inline]
     pub fn new_discrete_state(state: impl Into<super::components::DiscreteState>) -> Self {
         WaveformPoint::DiscreteState(state.into())
     }
 
+    #[inline]
+    pub fn new_discrete_states(
+        states: impl IntoIterator<Item = impl Into<super::components::DiscreteState>>,
+    ) -> Self {
+        WaveformPoint::DiscreteStates(states.into_iter().map(Into::into).collect())
+    }
+
     #[inline]
     pub fn new_discrete_state_init(
         state_init: impl Into<super::components::DiscreteStateInit>,
@@ -36,6 +64,34 @@ impl WaveformPoint {
     pub fn new_event(event: impl Into<super::components::Event>) -> Self {
         WaveformPoint::Event(event.into())
     }
+
+    #[inline]
+    pub fn new_bus_value(value: u64, width: u8) -> Self {
+        WaveformPoint::BusValue(super::components::BusValue::new(value, width))
+    }
+
+    #[inline]
+    pub fn new_logic_bit(state: impl Into<super::components::LogicBit>) -> Self {
+        WaveformPoint::LogicBit(state.into())
+    }
+
+    #[inline]
+    pub fn new_event_span(
+        class_id: impl Into<re_types::components::ClassId>,
+        duration: i64,
+    ) -> Self {
+        WaveformPoint::EventSpan(super::components::EventSpan::new(class_id, duration))
+    }
+
+    #[inline]
+    pub fn new_text_state(text: impl Into<super::components::TextState>) -> Self {
+        WaveformPoint::TextState(text.into())
+    }
+
+    #[inline]
+    pub fn new_sample_block(period: i64, values: impl Into<Vec<f64>>) -> Self {
+        WaveformPoint::SampleBlock(super::components::SampleBlock::new(period, values))
+    }
 }
 
 impl From<super::components::Scalar> for WaveformPoint {
@@ -73,12 +129,49 @@ impl From<super::components::DiscreteStateNormal> for WaveformPoint {
     }
 }
 
+impl From<super::components::BusValue> for WaveformPoint {
+    #[inline]
+    fn from(value: super::components::BusValue) -> Self {
+        WaveformPoint::BusValue(value)
+    }
+}
+
+impl From<super::components::LogicBit> for WaveformPoint {
+    #[inline]
+    fn from(value: super::components::LogicBit) -> Self {
+        WaveformPoint::LogicBit(value)
+    }
+}
+
+impl From<super::components::EventSpan> for WaveformPoint {
+    #[inline]
+    fn from(value: super::components::EventSpan) -> Self {
+        WaveformPoint::EventSpan(value)
+    }
+}
+
+impl From<super::components::TextState> for WaveformPoint {
+    #[inline]
+    fn from(value: super::components::TextState) -> Self {
+        WaveformPoint::TextState(value)
+    }
+}
+
+impl From<super::components::SampleBlock> for WaveformPoint {
+    #[inline]
+    fn from(value: super::components::SampleBlock) -> Self {
+        WaveformPoint::SampleBlock(value)
+    }
+}
+
 impl re_types::SizeBytes for WaveformPoint {
     #[inline]
     fn heap_size_bytes(&self) -> u64 {
         match self {
             WaveformPoint::Scalar(scalar) => scalar.heap_size_bytes(),
+            WaveformPoint::Scalars(scalars) => scalars.heap_size_bytes(),
             WaveformPoint::DiscreteState(discrete_state) => discrete_state.heap_size_bytes(),
+            WaveformPoint::DiscreteStates(discrete_states) => discrete_states.heap_size_bytes(),
             WaveformPoint::DiscreteStateInit(discrete_state_init) => {
                 discrete_state_init.heap_size_bytes()
             }
@@ -86,6 +179,11 @@ impl re_types::SizeBytes for WaveformPoint {
                 discrete_state_normal.heap_size_bytes()
             }
             WaveformPoint::Event(event) => event.heap_size_bytes(),
+            WaveformPoint::BusValue(bus_value) => bus_value.heap_size_bytes(),
+            WaveformPoint::LogicBit(logic_bit) => logic_bit.heap_size_bytes(),
+            WaveformPoint::EventSpan(event_span) => event_span.heap_size_bytes(),
+            WaveformPoint::TextState(text_state) => text_state.heap_size_bytes(),
+            WaveformPoint::SampleBlock(sample_block) => sample_block.heap_size_bytes(),
         }
     }
 
@@ -101,7 +199,7 @@ static REQUIRED_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 1usize]> =
 static RECOMMENDED_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 0usize]> =
     once_cell::sync::Lazy::new(|| []);
 
-static OPTIONAL_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 5usize]> =
+static OPTIONAL_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 10usize]> =
     once_cell::sync::Lazy::new(|| {
         [
             "wf.components.Scalar".into(),
@@ -109,10 +207,15 @@ static OPTIONAL_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 5usize]> =
             "wf.components.DiscreteStateInit".into(),
             "wf.components.DiscreteStateNormal".into(),
             "wf.components.Event".into(),
+            "wf.components.BusValue".into(),
+            "wf.components.LogicBit".into(),
+            "wf.components.EventSpan".into(),
+            "wf.components.TextState".into(),
+            "wf.components.SampleBlock".into(),
         ]
     });
 
-static ALL_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 6usize]> =
+static ALL_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 11usize]> =
     once_cell::sync::Lazy::new(|| {
         [
             "wf.components.WaveformPointIndicator".into(),
@@ -121,6 +224,11 @@ static ALL_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 6usize]> =
             "wf.components.DiscreteStateInit".into(),
             "wf.components.DiscreteStateNormal".into(),
             "wf.components.Event".into(),
+            "wf.components.BusValue".into(),
+            "wf.components.LogicBit".into(),
+            "wf.components.EventSpan".into(),
+            "wf.components.TextState".into(),
+            "wf.components.SampleBlock".into(),
         ]
     });
 
@@ -178,6 +286,20 @@ impl re_types::AsComponents for WaveformPoint {
             .into_iter()
             .flatten()
             .collect(),
+            WaveformPoint::Scalars(scalars) => vec![
+                Some(Self::indicator()),
+                Some((scalars as &dyn re_types::ComponentBatch).into()),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+            WaveformPoint::DiscreteStates(states) => vec![
+                Some(Self::indicator()),
+                Some((states as &dyn re_types::ComponentBatch).into()),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
             WaveformPoint::DiscreteState(state) => vec![
                 Some(Self::indicator()),
                 Some((state as &dyn re_types::ComponentBatch).into()),
@@ -206,6 +328,406 @@ impl re_types::AsComponents for WaveformPoint {
             .into_iter()
             .flatten()
             .collect(),
+            WaveformPoint::BusValue(bus_value) => vec![
+                Some(Self::indicator()),
+                Some((bus_value as &dyn re_types::ComponentBatch).into()),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+            WaveformPoint::LogicBit(logic_bit) => vec![
+                Some(Self::indicator()),
+                Some((logic_bit as &dyn re_types::ComponentBatch).into()),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+            WaveformPoint::EventSpan(event_span) => vec![
+                Some(Self::indicator()),
+                Some((event_span as &dyn re_types::ComponentBatch).into()),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+            WaveformPoint::TextState(text_state) => vec![
+                Some(Self::indicator()),
+                Some((text_state as &dyn re_types::ComponentBatch).into()),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+            WaveformPoint::SampleBlock(sample_block) => vec![
+                Some(Self::indicator()),
+                Some((sample_block as &dyn re_types::ComponentBatch).into()),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
         }
     }
 }
+
+/// Several waveform components logged as a single row, e.g. a discrete state with its init and
+/// normal state, or a scalar with its color.
+///
+/// Entities logged with it are drawn by the waveform view like those logged with
+/// [`WaveformPoint`], whose indicator it carries.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WaveformSample {
+    pub scalar: Option<super::components::Scalar>,
+    pub state: Option<super::components::DiscreteState>,
+    pub init: Option<super::components::DiscreteStateInit>,
+    pub normal: Option<super::components::DiscreteStateNormal>,
+    pub event: Option<super::components::Event>,
+
+    /// Color of the entity's channel, overriding the automatic one
+    pub color: Option<re_types::components::Color>,
+
+    /// Physical unit of the calibrated scalars
+    pub unit: Option<super::components::Unit>,
+
+    /// Calibration of the scalars, drawn as `raw * scale + offset`
+    pub scale: Option<super::components::Scale>,
+    pub offset: Option<super::components::Offset>,
+
+    /// How the scalars are joined between samples
+    pub interpolation: Option<super::components::Interpolation>,
+}
+
+impl WaveformSample {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn with_scalar(mut self, scalar: impl Into<super::components::Scalar>) -> Self {
+        self.scalar = Some(scalar.into());
+        self
+    }
+
+    #[inline]
+    pub fn with_state(mut self, state: impl Into<super::components::DiscreteState>) -> Self {
+        self.state = Some(state.into());
+        self
+    }
+
+    #[inline]
+    pub fn with_init(mut self, init: impl Into<super::components::DiscreteStateInit>) -> Self {
+        self.init = Some(init.into());
+        self
+    }
+
+    #[inline]
+    pub fn with_normal(
+        mut self,
+        normal: impl Into<super::components::DiscreteStateNormal>,
+    ) -> Self {
+        self.normal = Some(normal.into());
+        self
+    }
+
+    #[inline]
+    pub fn with_event(mut self, event: impl Into<super::components::Event>) -> Self {
+        self.event = Some(event.into());
+        self
+    }
+
+    #[inline]
+    pub fn with_color(mut self, color: impl Into<re_types::components::Color>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    #[inline]
+    pub fn with_unit(mut self, unit: impl Into<super::components::Unit>) -> Self {
+        self.unit = Some(unit.into());
+        self
+    }
+
+    /// Calibrates raw scalars to `raw * scale + offset`
+    #[inline]
+    pub fn with_calibration(mut self, scale: f64, offset: f64) -> Self {
+        self.scale = Some(scale.into());
+        self.offset = Some(offset.into());
+        self
+    }
+
+    #[inline]
+    pub fn with_interpolation(
+        mut self,
+        interpolation: impl Into<super::components::Interpolation>,
+    ) -> Self {
+        self.interpolation = Some(interpolation.into());
+        self
+    }
+}
+
+impl re_types::SizeBytes for WaveformSample {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        self.scalar.heap_size_bytes()
+            + self.state.heap_size_bytes()
+            + self.init.heap_size_bytes()
+            + self.normal.heap_size_bytes()
+            + self.event.heap_size_bytes()
+            + self.color.heap_size_bytes()
+            + self.unit.heap_size_bytes()
+            + self.scale.heap_size_bytes()
+            + self.offset.heap_size_bytes()
+            + self.interpolation.heap_size_bytes()
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        false
+    }
+}
+
+static SAMPLE_OPTIONAL_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 10usize]> =
+    once_cell::sync::Lazy::new(|| {
+        [
+            "wf.components.Scalar".into(),
+            "wf.components.DiscreteState".into(),
+            "wf.components.DiscreteStateInit".into(),
+            "wf.components.DiscreteStateNormal".into(),
+            "wf.components.Event".into(),
+            "rerun.components.Color".into(),
+            "wf.components.Unit".into(),
+            "wf.components.Scale".into(),
+            "wf.components.Offset".into(),
+            "wf.components.Interpolation".into(),
+        ]
+    });
+
+static SAMPLE_ALL_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 11usize]> =
+    once_cell::sync::Lazy::new(|| {
+        [
+            "wf.components.WaveformPointIndicator".into(),
+            "wf.components.Scalar".into(),
+            "wf.components.DiscreteState".into(),
+            "wf.components.DiscreteStateInit".into(),
+            "wf.components.DiscreteStateNormal".into(),
+            "wf.components.Event".into(),
+            "rerun.components.Color".into(),
+            "wf.components.Unit".into(),
+            "wf.components.Scale".into(),
+            "wf.components.Offset".into(),
+            "wf.components.Interpolation".into(),
+        ]
+    });
+
+impl WaveformSample {
+    /// The total number of components in the archetype: 1 required, 0 recommended, 10 optional
+    pub const NUM_COMPONENTS: usize = 11usize;
+}
+
+impl re_types::Archetype for WaveformSample {
+    /// Shares the indicator of [`WaveformPoint`], so it is visualized the same way
+    type Indicator = WaveformPointIndicator;
+
+    fn name() -> re_sdk::ArchetypeName {
+        "wf.archetypes.WaveformSample".into()
+    }
+
+    fn required_components() -> std::borrow::Cow<'static, [ComponentName]> {
+        REQUIRED_COMPONENTS.as_slice().into()
+    }
+
+    fn recommended_components() -> std::borrow::Cow<'static, [ComponentName]> {
+        RECOMMENDED_COMPONENTS.as_slice().into()
+    }
+
+    fn optional_components() -> std::borrow::Cow<'static, [ComponentName]> {
+        SAMPLE_OPTIONAL_COMPONENTS.as_slice().into()
+    }
+
+    fn all_components() -> std::borrow::Cow<'static, [ComponentName]> {
+        SAMPLE_ALL_COMPONENTS.as_slice().into()
+    }
+
+    fn indicator() -> re_sdk::MaybeOwnedComponentBatch<'static> {
+        re_sdk::MaybeOwnedComponentBatch::Owned(
+            Box::<<Self as re_sdk::Archetype>::Indicator>::default(),
+        )
+    }
+
+    fn display_name() -> &'static str {
+        "WaveformSample"
+    }
+}
+
+impl re_types::AsComponents for WaveformSample {
+    fn as_component_batches(&self) -> Vec<re_sdk::MaybeOwnedComponentBatch<'_>> {
+        re_tracing::profile_function!();
+        use re_types::Archetype as _;
+        [
+            Some(Self::indicator()),
+            self.scalar
+                .as_ref()
+                .map(|comp| (comp as &dyn re_types::ComponentBatch).into()),
+            self.state
+                .as_ref()
+                .map(|comp| (comp as &dyn re_types::ComponentBatch).into()),
+            self.init
+                .as_ref()
+                .map(|comp| (comp as &dyn re_types::ComponentBatch).into()),
+            self.normal
+                .as_ref()
+                .map(|comp| (comp as &dyn re_types::ComponentBatch).into()),
+            self.event
+                .as_ref()
+                .map(|comp| (comp as &dyn re_types::ComponentBatch).into()),
+            self.color
+                .as_ref()
+                .map(|comp| (comp as &dyn re_types::ComponentBatch).into()),
+            self.unit
+                .as_ref()
+                .map(|comp| (comp as &dyn re_types::ComponentBatch).into()),
+            self.scale
+                .as_ref()
+                .map(|comp| (comp as &dyn re_types::ComponentBatch).into()),
+            self.offset
+                .as_ref()
+                .map(|comp| (comp as &dyn re_types::ComponentBatch).into()),
+            self.interpolation
+                .as_ref()
+                .map(|comp| (comp as &dyn re_types::ComponentBatch).into()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+/// Maximum number of rows sent in a single chunk by the column helpers
+pub const COLUMN_CHUNK_MAX_ROWS: usize = 65_536;
+
+/// Logs `values` at `times` on `timeline` as scalars of `entity_path`.
+///
+/// Rows are packed into chunks of up to [`COLUMN_CHUNK_MAX_ROWS`] rows that are sent as a whole,
+/// which is much faster than logging them one at a time. `times` and `values` are paired up to
+/// the shorter of them.
+pub fn send_scalar_column(
+    rec: &re_sdk::RecordingStream,
+    entity_path: impl Into<re_sdk::EntityPath>,
+    timeline: re_sdk::Timeline,
+    times: &[i64],
+    values: &[f64],
+) -> re_chunk::ChunkResult<()> {
+    send_column(
+        rec,
+        entity_path.into(),
+        timeline,
+        times,
+        values
+            .iter()
+            .map(|&value| super::components::Scalar::from(value)),
+    )
+}
+
+/// Logs `class_ids` at `times` on `timeline` as discrete states of `entity_path`, see
+/// [`send_scalar_column`]
+pub fn send_state_column(
+    rec: &re_sdk::RecordingStream,
+    entity_path: impl Into<re_sdk::EntityPath>,
+    timeline: re_sdk::Timeline,
+    times: &[i64],
+    class_ids: &[u16],
+) -> re_chunk::ChunkResult<()> {
+    send_column(
+        rec,
+        entity_path.into(),
+        timeline,
+        times,
+        class_ids
+            .iter()
+            .map(|&class_id| super::components::DiscreteState::from(class_id)),
+    )
+}
+
+/// Logs `class_ids` at `times` on `timeline` as events of `entity_path`, see
+/// [`send_scalar_column`]
+pub fn send_event_column(
+    rec: &re_sdk::RecordingStream,
+    entity_path: impl Into<re_sdk::EntityPath>,
+    timeline: re_sdk::Timeline,
+    times: &[i64],
+    class_ids: &[u16],
+) -> re_chunk::ChunkResult<()> {
+    send_column(
+        rec,
+        entity_path.into(),
+        timeline,
+        times,
+        class_ids
+            .iter()
+            .map(|&class_id| super::components::Event::from(class_id)),
+    )
+}
+
+/// Sends one row per time holding the value and the [`WaveformPoint`] indicator.
+///
+/// The SDK of this Rerun version has no `send_columns` yet, so each chunk is built directly from
+/// one time column and one array per component, and handed to the recording stream, bypassing
+/// its batcher.
+fn send_column<C: re_types::Component + Clone>(
+    rec: &re_sdk::RecordingStream,
+    entity_path: re_sdk::EntityPath,
+    timeline: re_sdk::Timeline,
+    times: &[i64],
+    values: impl IntoIterator<Item = C>,
+) -> re_chunk::ChunkResult<()> {
+    use arrow2::{
+        array::{Array, ListArray, NullArray, PrimitiveArray},
+        datatypes::DataType,
+        offset::Offsets,
+    };
+    use itertools::Itertools as _;
+    use re_types::{external::arrow2, ComponentBatch as _};
+
+    // Every row holds a single instance of the component
+    let column = |values: Box<dyn Array>| -> re_chunk::ChunkResult<ListArray<i32>> {
+        let offsets = Offsets::try_from_lengths(std::iter::repeat(1).take(values.len()))?;
+        Ok(ListArray::new(
+            ListArray::<i32>::default_datatype(values.data_type().clone()),
+            offsets.into(),
+            values,
+            None,
+        ))
+    };
+
+    let indicator_name = WaveformPointIndicator::DEFAULT.name();
+    let rows = times.iter().zip(values);
+
+    for rows in &rows.chunks(COLUMN_CHUNK_MAX_ROWS) {
+        let (times, values): (Vec<i64>, Vec<C>) = rows.map(|(&time, value)| (time, value)).unzip();
+        let len = times.len();
+
+        let timelines = [(
+            timeline,
+            re_chunk::ChunkTimeline::new(None, timeline, PrimitiveArray::from_vec(times)),
+        )]
+        .into();
+        let components = [
+            (
+                indicator_name,
+                column(NullArray::new(DataType::Null, len).boxed())?,
+            ),
+            (
+                C::name(),
+                column(C::to_arrow(values.iter().map(std::borrow::Cow::Borrowed))?)?,
+            ),
+        ]
+        .into();
+
+        rec.send_chunk(re_chunk::Chunk::from_auto_row_ids(
+            re_chunk::ChunkId::new(),
+            entity_path.clone(),
+            timelines,
+            components,
+        )?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,358 @@
+use re_types::ComponentName;
+
+use super::components::{
+    BusRadix, DomainHeight, DomainOrder, GroupingDepth, LaneRule, SecondMarker, SelectedEntity,
+    SidePanelFraction, YAxisDomain,
+};
+
+/// View state of a waveform space view, stored as a view property in the blueprint.
+///
+/// The viewer keeps it at `space_view/<space view id>/WaveformViewState` in the blueprint store,
+/// which is also where the SDK's blueprint API logs view properties. Unset components keep the
+/// viewer's defaults.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WaveformViewState {
+    /// Time of the secondary marker
+    pub second_marker: Option<SecondMarker>,
+
+    /// Domains from top to bottom, domains not listed are shown after these
+    pub domain_order: Option<Vec<DomainOrder>>,
+
+    /// Entities shown in selected mode, selected mode is off when empty
+    pub selected_entities: Option<Vec<SelectedEntity>>,
+
+    /// Fraction of the view width taken by the side panel
+    pub side_panel_fraction: Option<SidePanelFraction>,
+
+    /// Relative heights of the domains in `domain_order`
+    pub domain_heights: Option<Vec<DomainHeight>>,
+
+    /// Radix bus values are labelled in, hexadecimal when unset
+    pub bus_radix: Option<BusRadix>,
+
+    /// Domains drawn with a y-axis
+    pub y_axis_domains: Option<Vec<YAxisDomain>>,
+}
+
+impl WaveformViewState {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn with_second_marker(mut self, second_marker: impl Into<SecondMarker>) -> Self {
+        self.second_marker = Some(second_marker.into());
+        self
+    }
+
+    #[inline]
+    pub fn with_domain_order(
+        mut self,
+        domain_order: impl IntoIterator<Item = impl Into<DomainOrder>>,
+    ) -> Self {
+        self.domain_order = Some(domain_order.into_iter().map(Into::into).collect());
+        self
+    }
+
+    #[inline]
+    pub fn with_selected_entities(
+        mut self,
+        selected_entities: impl IntoIterator<Item = impl Into<SelectedEntity>>,
+    ) -> Self {
+        self.selected_entities = Some(selected_entities.into_iter().map(Into::into).collect());
+        self
+    }
+
+    #[inline]
+    pub fn with_side_panel_fraction(
+        mut self,
+        side_panel_fraction: impl Into<SidePanelFraction>,
+    ) -> Self {
+        self.side_panel_fraction = Some(side_panel_fraction.into());
+        self
+    }
+
+    #[inline]
+    pub fn with_domain_heights(
+        mut self,
+        domain_heights: impl IntoIterator<Item = impl Into<DomainHeight>>,
+    ) -> Self {
+        self.domain_heights = Some(domain_heights.into_iter().map(Into::into).collect());
+        self
+    }
+
+    #[inline]
+    pub fn with_bus_radix(mut self, bus_radix: impl Into<BusRadix>) -> Self {
+        self.bus_radix = Some(bus_radix.into());
+        self
+    }
+
+    #[inline]
+    pub fn with_y_axis_domains(
+        mut self,
+        y_axis_domains: impl IntoIterator<Item = impl Into<YAxisDomain>>,
+    ) -> Self {
+        self.y_axis_domains = Some(y_axis_domains.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+impl re_types::SizeBytes for WaveformViewState {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        self.second_marker.heap_size_bytes()
+            + self.domain_order.heap_size_bytes()
+            + self.selected_entities.heap_size_bytes()
+            + self.side_panel_fraction.heap_size_bytes()
+            + self.domain_heights.heap_size_bytes()
+            + self.bus_radix.heap_size_bytes()
+            + self.y_axis_domains.heap_size_bytes()
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        false
+    }
+}
+
+static REQUIRED_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 0usize]> =
+    once_cell::sync::Lazy::new(|| []);
+
+static RECOMMENDED_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 1usize]> =
+    once_cell::sync::Lazy::new(|| ["wf.blueprint.components.WaveformViewStateIndicator".into()]);
+
+static OPTIONAL_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 7usize]> =
+    once_cell::sync::Lazy::new(|| {
+        [
+            "wf.blueprint.components.SecondMarker".into(),
+            "wf.blueprint.components.DomainOrder".into(),
+            "wf.blueprint.components.SelectedEntity".into(),
+            "wf.blueprint.components.SidePanelFraction".into(),
+            "wf.blueprint.components.DomainHeight".into(),
+            "wf.blueprint.components.BusRadix".into(),
+            "wf.blueprint.components.YAxisDomain".into(),
+        ]
+    });
+
+static ALL_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 8usize]> =
+    once_cell::sync::Lazy::new(|| {
+        [
+            "wf.blueprint.components.WaveformViewStateIndicator".into(),
+            "wf.blueprint.components.SecondMarker".into(),
+            "wf.blueprint.components.DomainOrder".into(),
+            "wf.blueprint.components.SelectedEntity".into(),
+            "wf.blueprint.components.SidePanelFraction".into(),
+            "wf.blueprint.components.DomainHeight".into(),
+            "wf.blueprint.components.BusRadix".into(),
+            "wf.blueprint.components.YAxisDomain".into(),
+        ]
+    });
+
+impl WaveformViewState {
+    /// The total number of components in the archetype: 0 required, 1 recommended, 7 optional
+    pub const NUM_COMPONENTS: usize = 8usize;
+}
+
+/// Indicator component for the [`WaveformViewState`] [`re_types::Archetype`]
+pub type WaveformViewStateIndicator = re_types::GenericIndicatorComponent<WaveformViewState>;
+
+impl re_types::Archetype for WaveformViewState {
+    type Indicator = WaveformViewStateIndicator;
+
+    fn name() -> re_sdk::ArchetypeName {
+        "wf.blueprint.archetypes.WaveformViewState".into()
+    }
+
+    fn required_components() -> std::borrow::Cow<'static, [ComponentName]> {
+        REQUIRED_COMPONENTS.as_slice().into()
+    }
+
+    fn recommended_components() -> std::borrow::Cow<'static, [ComponentName]> {
+        RECOMMENDED_COMPONENTS.as_slice().into()
+    }
+
+    fn optional_components() -> std::borrow::Cow<'static, [ComponentName]> {
+        OPTIONAL_COMPONENTS.as_slice().into()
+    }
+
+    fn all_components() -> std::borrow::Cow<'static, [ComponentName]> {
+        ALL_COMPONENTS.as_slice().into()
+    }
+
+    fn indicator() -> re_sdk::MaybeOwnedComponentBatch<'static> {
+        re_sdk::MaybeOwnedComponentBatch::Owned(
+            Box::<<Self as re_sdk::Archetype>::Indicator>::default(),
+        )
+    }
+
+    fn display_name() -> &'static str {
+        "WaveformViewState"
+    }
+}
+
+impl re_types::AsComponents for WaveformViewState {
+    fn as_component_batches(&self) -> Vec<re_sdk::MaybeOwnedComponentBatch<'_>> {
+        re_tracing::profile_function!();
+        use re_types::Archetype as _;
+        [
+            Some(Self::indicator()),
+            self.second_marker
+                .as_ref()
+                .map(|comp| (comp as &dyn re_types::ComponentBatch).into()),
+            self.domain_order
+                .as_ref()
+                .map(|comp_batch| (comp_batch as &dyn re_types::ComponentBatch).into()),
+            self.selected_entities
+                .as_ref()
+                .map(|comp_batch| (comp_batch as &dyn re_types::ComponentBatch).into()),
+            self.side_panel_fraction
+                .as_ref()
+                .map(|comp| (comp as &dyn re_types::ComponentBatch).into()),
+            self.domain_heights
+                .as_ref()
+                .map(|comp_batch| (comp_batch as &dyn re_types::ComponentBatch).into()),
+            self.bus_radix
+                .as_ref()
+                .map(|comp| (comp as &dyn re_types::ComponentBatch).into()),
+            self.y_axis_domains
+                .as_ref()
+                .map(|comp_batch| (comp_batch as &dyn re_types::ComponentBatch).into()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+/// How a waveform space view groups entities into domains, stored as a view property in the
+/// blueprint at `space_view/<space view id>/WaveformGrouping`.
+///
+/// An entity goes to the lane of the first rule whose filter matches it, otherwise to the domain
+/// named by the first `depth` parts of its path.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WaveformGrouping {
+    /// Number of leading entity path parts that name a domain, 1 when unset
+    pub depth: Option<GroupingDepth>,
+
+    /// Named lanes, checked in order
+    pub lane_rules: Option<Vec<LaneRule>>,
+}
+
+impl WaveformGrouping {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn with_depth(mut self, depth: impl Into<GroupingDepth>) -> Self {
+        self.depth = Some(depth.into());
+        self
+    }
+
+    #[inline]
+    pub fn with_lane_rules(
+        mut self,
+        lane_rules: impl IntoIterator<Item = impl Into<LaneRule>>,
+    ) -> Self {
+        self.lane_rules = Some(lane_rules.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+impl re_types::SizeBytes for WaveformGrouping {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        self.depth.heap_size_bytes() + self.lane_rules.heap_size_bytes()
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        false
+    }
+}
+
+static GROUPING_REQUIRED_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 0usize]> =
+    once_cell::sync::Lazy::new(|| []);
+
+static GROUPING_RECOMMENDED_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 1usize]> =
+    once_cell::sync::Lazy::new(|| ["wf.blueprint.components.WaveformGroupingIndicator".into()]);
+
+static GROUPING_OPTIONAL_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 2usize]> =
+    once_cell::sync::Lazy::new(|| {
+        [
+            "wf.blueprint.components.GroupingDepth".into(),
+            "wf.blueprint.components.LaneRule".into(),
+        ]
+    });
+
+static GROUPING_ALL_COMPONENTS: once_cell::sync::Lazy<[ComponentName; 3usize]> =
+    once_cell::sync::Lazy::new(|| {
+        [
+            "wf.blueprint.components.WaveformGroupingIndicator".into(),
+            "wf.blueprint.components.GroupingDepth".into(),
+            "wf.blueprint.components.LaneRule".into(),
+        ]
+    });
+
+impl WaveformGrouping {
+    /// The total number of components in the archetype: 0 required, 1 recommended, 2 optional
+    pub const NUM_COMPONENTS: usize = 3usize;
+}
+
+/// Indicator component for the [`WaveformGrouping`] [`re_types::Archetype`]
+pub type WaveformGroupingIndicator = re_types::GenericIndicatorComponent<WaveformGrouping>;
+
+impl re_types::Archetype for WaveformGrouping {
+    type Indicator = WaveformGroupingIndicator;
+
+    fn name() -> re_sdk::ArchetypeName {
+        "wf.blueprint.archetypes.WaveformGrouping".into()
+    }
+
+    fn required_components() -> std::borrow::Cow<'static, [ComponentName]> {
+        GROUPING_REQUIRED_COMPONENTS.as_slice().into()
+    }
+
+    fn recommended_components() -> std::borrow::Cow<'static, [ComponentName]> {
+        GROUPING_RECOMMENDED_COMPONENTS.as_slice().into()
+    }
+
+    fn optional_components() -> std::borrow::Cow<'static, [ComponentName]> {
+        GROUPING_OPTIONAL_COMPONENTS.as_slice().into()
+    }
+
+    fn all_components() -> std::borrow::Cow<'static, [ComponentName]> {
+        GROUPING_ALL_COMPONENTS.as_slice().into()
+    }
+
+    fn indicator() -> re_sdk::MaybeOwnedComponentBatch<'static> {
+        re_sdk::MaybeOwnedComponentBatch::Owned(
+            Box::<<Self as re_sdk::Archetype>::Indicator>::default(),
+        )
+    }
+
+    fn display_name() -> &'static str {
+        "WaveformGrouping"
+    }
+}
+
+impl re_types::AsComponents for WaveformGrouping {
+    fn as_component_batches(&self) -> Vec<re_sdk::MaybeOwnedComponentBatch<'_>> {
+        re_tracing::profile_function!();
+        use re_types::Archetype as _;
+        [
+            Some(Self::indicator()),
+            self.depth
+                .as_ref()
+                .map(|comp| (comp as &dyn re_types::ComponentBatch).into()),
+            self.lane_rules
+                .as_ref()
+                .map(|comp_batch| (comp_batch as &dyn re_types::ComponentBatch).into()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
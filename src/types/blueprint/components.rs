@@ -0,0 +1,531 @@
+use itertools::Itertools;
+use re_types::{
+    datatypes::{Float32, TimeInt, UInt32, Utf8},
+    external::arrow2,
+};
+
+/// Time of the secondary marker on the view's timeline
+#[derive(Clone, Debug, PartialEq)]
+pub struct SecondMarker(pub TimeInt);
+
+impl re_types::SizeBytes for SecondMarker {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        self.0.heap_size_bytes()
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        <TimeInt>::is_pod()
+    }
+}
+
+impl<T: Into<TimeInt>> From<T> for SecondMarker {
+    fn from(v: T) -> Self {
+        Self(v.into())
+    }
+}
+
+re_types::macros::impl_into_cow!(SecondMarker);
+
+impl re_types::Loggable for SecondMarker {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.blueprint.components.SecondMarker".into()
+    }
+
+    #[allow(clippy::wildcard_imports)]
+    #[inline]
+    fn arrow_datatype() -> re_types::external::arrow2::datatypes::DataType {
+        TimeInt::arrow_datatype()
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        let data = data.into_iter().map(|d| d.map(|d| d.into().0));
+        TimeInt::to_arrow_opt(data)
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        TimeInt::from_arrow_opt(data).map(|v| {
+            v.into_iter()
+                .map(|v| v.map(|v| SecondMarker(v)))
+                .collect_vec()
+        })
+    }
+}
+
+/// Domain shown at the index of this instance, top to bottom
+#[derive(Clone, Debug, PartialEq)]
+pub struct DomainOrder(pub Utf8);
+
+impl re_types::SizeBytes for DomainOrder {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        self.0.heap_size_bytes()
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        <Utf8>::is_pod()
+    }
+}
+
+impl<T: Into<Utf8>> From<T> for DomainOrder {
+    fn from(v: T) -> Self {
+        Self(v.into())
+    }
+}
+
+re_types::macros::impl_into_cow!(DomainOrder);
+
+impl re_types::Loggable for DomainOrder {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.blueprint.components.DomainOrder".into()
+    }
+
+    #[allow(clippy::wildcard_imports)]
+    #[inline]
+    fn arrow_datatype() -> re_types::external::arrow2::datatypes::DataType {
+        Utf8::arrow_datatype()
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        let data = data.into_iter().map(|d| d.map(|d| d.into().into_owned().0));
+        Utf8::to_arrow_opt(data)
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        Utf8::from_arrow_opt(data).map(|v| {
+            v.into_iter()
+                .map(|v| v.map(|v| DomainOrder(v)))
+                .collect_vec()
+        })
+    }
+}
+
+/// Entity shown in selected mode, the view is in selected mode while there is at least one
+#[derive(Clone, Debug, PartialEq)]
+pub struct SelectedEntity(pub Utf8);
+
+impl re_types::SizeBytes for SelectedEntity {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        self.0.heap_size_bytes()
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        <Utf8>::is_pod()
+    }
+}
+
+impl<T: Into<Utf8>> From<T> for SelectedEntity {
+    fn from(v: T) -> Self {
+        Self(v.into())
+    }
+}
+
+re_types::macros::impl_into_cow!(SelectedEntity);
+
+impl re_types::Loggable for SelectedEntity {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.blueprint.components.SelectedEntity".into()
+    }
+
+    #[allow(clippy::wildcard_imports)]
+    #[inline]
+    fn arrow_datatype() -> re_types::external::arrow2::datatypes::DataType {
+        Utf8::arrow_datatype()
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        let data = data.into_iter().map(|d| d.map(|d| d.into().into_owned().0));
+        Utf8::to_arrow_opt(data)
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        Utf8::from_arrow_opt(data).map(|v| {
+            v.into_iter()
+                .map(|v| v.map(|v| SelectedEntity(v)))
+                .collect_vec()
+        })
+    }
+}
+
+/// Number of leading entity path parts that name the domain of an entity
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupingDepth(pub UInt32);
+
+impl re_types::SizeBytes for GroupingDepth {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        self.0.heap_size_bytes()
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        <UInt32>::is_pod()
+    }
+}
+
+impl<T: Into<UInt32>> From<T> for GroupingDepth {
+    fn from(v: T) -> Self {
+        Self(v.into())
+    }
+}
+
+re_types::macros::impl_into_cow!(GroupingDepth);
+
+impl re_types::Loggable for GroupingDepth {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.blueprint.components.GroupingDepth".into()
+    }
+
+    #[allow(clippy::wildcard_imports)]
+    #[inline]
+    fn arrow_datatype() -> re_types::external::arrow2::datatypes::DataType {
+        UInt32::arrow_datatype()
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        let data = data.into_iter().map(|d| d.map(|d| d.into().0));
+        UInt32::to_arrow_opt(data)
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        UInt32::from_arrow_opt(data).map(|v| {
+            v.into_iter()
+                .map(|v| v.map(|v| GroupingDepth(v)))
+                .collect_vec()
+        })
+    }
+}
+
+/// Named lane and the entities it holds, as `name = filter`, with the entity path filter rules
+/// separated by commas
+#[derive(Clone, Debug, PartialEq)]
+pub struct LaneRule(pub Utf8);
+
+impl re_types::SizeBytes for LaneRule {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        self.0.heap_size_bytes()
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        <Utf8>::is_pod()
+    }
+}
+
+impl<T: Into<Utf8>> From<T> for LaneRule {
+    fn from(v: T) -> Self {
+        Self(v.into())
+    }
+}
+
+re_types::macros::impl_into_cow!(LaneRule);
+
+impl re_types::Loggable for LaneRule {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.blueprint.components.LaneRule".into()
+    }
+
+    #[allow(clippy::wildcard_imports)]
+    #[inline]
+    fn arrow_datatype() -> re_types::external::arrow2::datatypes::DataType {
+        Utf8::arrow_datatype()
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        let data = data.into_iter().map(|d| d.map(|d| d.into().into_owned().0));
+        Utf8::to_arrow_opt(data)
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        Utf8::from_arrow_opt(data)
+            .map(|v| v.into_iter().map(|v| v.map(|v| LaneRule(v))).collect_vec())
+    }
+}
+
+/// Fraction of the view width taken by the side panel
+#[derive(Clone, Debug, PartialEq)]
+pub struct SidePanelFraction(pub Float32);
+
+impl re_types::SizeBytes for SidePanelFraction {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        self.0.heap_size_bytes()
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        <Float32>::is_pod()
+    }
+}
+
+impl<T: Into<Float32>> From<T> for SidePanelFraction {
+    fn from(v: T) -> Self {
+        Self(v.into())
+    }
+}
+
+re_types::macros::impl_into_cow!(SidePanelFraction);
+
+impl re_types::Loggable for SidePanelFraction {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.blueprint.components.SidePanelFraction".into()
+    }
+
+    #[allow(clippy::wildcard_imports)]
+    #[inline]
+    fn arrow_datatype() -> re_types::external::arrow2::datatypes::DataType {
+        Float32::arrow_datatype()
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        let data = data.into_iter().map(|d| d.map(|d| d.into().0));
+        Float32::to_arrow_opt(data)
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        Float32::from_arrow_opt(data).map(|v| {
+            v.into_iter()
+                .map(|v| v.map(|v| SidePanelFraction(v)))
+                .collect_vec()
+        })
+    }
+}
+
+/// Relative height of the domain at the same index in the domain order, 1 by default
+#[derive(Clone, Debug, PartialEq)]
+pub struct DomainHeight(pub Float32);
+
+impl re_types::SizeBytes for DomainHeight {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        self.0.heap_size_bytes()
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        <Float32>::is_pod()
+    }
+}
+
+impl<T: Into<Float32>> From<T> for DomainHeight {
+    fn from(v: T) -> Self {
+        Self(v.into())
+    }
+}
+
+re_types::macros::impl_into_cow!(DomainHeight);
+
+impl re_types::Loggable for DomainHeight {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.blueprint.components.DomainHeight".into()
+    }
+
+    #[allow(clippy::wildcard_imports)]
+    #[inline]
+    fn arrow_datatype() -> re_types::external::arrow2::datatypes::DataType {
+        Float32::arrow_datatype()
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        let data = data.into_iter().map(|d| d.map(|d| d.into().0));
+        Float32::to_arrow_opt(data)
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        Float32::from_arrow_opt(data).map(|v| {
+            v.into_iter()
+                .map(|v| v.map(|v| DomainHeight(v)))
+                .collect_vec()
+        })
+    }
+}
+
+/// Radix bus values are labelled in: 2, 8, 10 or 16
+#[derive(Clone, Debug, PartialEq)]
+pub struct BusRadix(pub UInt32);
+
+impl re_types::SizeBytes for BusRadix {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        self.0.heap_size_bytes()
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        <UInt32>::is_pod()
+    }
+}
+
+impl<T: Into<UInt32>> From<T> for BusRadix {
+    fn from(v: T) -> Self {
+        Self(v.into())
+    }
+}
+
+re_types::macros::impl_into_cow!(BusRadix);
+
+impl re_types::Loggable for BusRadix {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.blueprint.components.BusRadix".into()
+    }
+
+    #[allow(clippy::wildcard_imports)]
+    #[inline]
+    fn arrow_datatype() -> re_types::external::arrow2::datatypes::DataType {
+        UInt32::arrow_datatype()
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        let data = data.into_iter().map(|d| d.map(|d| d.into().0));
+        UInt32::to_arrow_opt(data)
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        UInt32::from_arrow_opt(data)
+            .map(|v| v.into_iter().map(|v| v.map(|v| BusRadix(v))).collect_vec())
+    }
+}
+
+/// Domain drawn with a y-axis
+#[derive(Clone, Debug, PartialEq)]
+pub struct YAxisDomain(pub Utf8);
+
+impl re_types::SizeBytes for YAxisDomain {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        self.0.heap_size_bytes()
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        <Utf8>::is_pod()
+    }
+}
+
+impl<T: Into<Utf8>> From<T> for YAxisDomain {
+    fn from(v: T) -> Self {
+        Self(v.into())
+    }
+}
+
+re_types::macros::impl_into_cow!(YAxisDomain);
+
+impl re_types::Loggable for YAxisDomain {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.blueprint.components.YAxisDomain".into()
+    }
+
+    #[allow(clippy::wildcard_imports)]
+    #[inline]
+    fn arrow_datatype() -> re_types::external::arrow2::datatypes::DataType {
+        Utf8::arrow_datatype()
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        let data = data.into_iter().map(|d| d.map(|d| d.into().into_owned().0));
+        Utf8::to_arrow_opt(data)
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        Utf8::from_arrow_opt(data).map(|v| {
+            v.into_iter()
+                .map(|v| v.map(|v| YAxisDomain(v)))
+                .collect_vec()
+        })
+    }
+}
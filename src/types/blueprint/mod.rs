@@ -0,0 +1,2 @@
+pub mod archetypes;
+pub mod components;
@@ -283,3 +283,1015 @@ impl re_types::Loggable for Event {
             .map(|v| v.into_iter().map(|v| v.map(|v| Event(v))).collect_vec())
     }
 }
+
+/// Value of a multi-bit bus: the low `width` bits of `value`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BusValue {
+    pub value: u64,
+    pub width: u8,
+}
+
+impl BusValue {
+    #[inline]
+    pub fn new(value: u64, width: u8) -> Self {
+        Self { value, width }
+    }
+}
+
+impl re_types::SizeBytes for BusValue {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        0
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        true
+    }
+}
+
+re_types::macros::impl_into_cow!(BusValue);
+
+impl re_types::Loggable for BusValue {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.components.BusValue".into()
+    }
+
+    #[inline]
+    fn arrow_datatype() -> arrow2::datatypes::DataType {
+        use arrow2::datatypes::{DataType, Field};
+        DataType::Struct(std::sync::Arc::new(vec![
+            Field::new("value", DataType::UInt64, false),
+            Field::new("width", DataType::UInt8, false),
+        ]))
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        use arrow2::{
+            array::{PrimitiveArray, StructArray},
+            bitmap::Bitmap,
+            datatypes::DataType,
+        };
+
+        let data: Vec<Option<BusValue>> = data
+            .into_iter()
+            .map(|d| d.map(|d| d.into().into_owned()))
+            .collect();
+
+        let validity: Option<Bitmap> = data
+            .iter()
+            .any(Option::is_none)
+            .then(|| data.iter().map(Option::is_some).collect());
+
+        let values = PrimitiveArray::new(
+            DataType::UInt64,
+            data.iter()
+                .map(|d| d.map_or(0, |d| d.value))
+                .collect::<Vec<_>>()
+                .into(),
+            validity.clone(),
+        );
+        let widths = PrimitiveArray::new(
+            DataType::UInt8,
+            data.iter()
+                .map(|d| d.map_or(0, |d| d.width))
+                .collect::<Vec<_>>()
+                .into(),
+            validity.clone(),
+        );
+
+        Ok(StructArray::new(
+            Self::arrow_datatype(),
+            vec![values.boxed(), widths.boxed()],
+            validity,
+        )
+        .boxed())
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        use arrow2::array::{PrimitiveArray, StructArray};
+
+        let mismatch = || {
+            re_types::DeserializationError::datatype_mismatch(
+                Self::arrow_datatype(),
+                data.data_type().clone(),
+            )
+        };
+
+        let data = data
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .ok_or_else(mismatch)?;
+
+        let [values, widths] = data.values() else {
+            return Err(mismatch());
+        };
+        let values = values
+            .as_any()
+            .downcast_ref::<PrimitiveArray<u64>>()
+            .ok_or_else(mismatch)?;
+        let widths = widths
+            .as_any()
+            .downcast_ref::<PrimitiveArray<u8>>()
+            .ok_or_else(mismatch)?;
+
+        Ok((0..data.len())
+            .map(|i| {
+                data.is_valid(i)
+                    .then(|| BusValue::new(values.value(i), widths.value(i)))
+            })
+            .collect())
+    }
+}
+
+/// State of a four-state logic signal, as in HDL simulations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogicState {
+    Low,
+    High,
+    /// Unknown, `X`
+    Unknown,
+    /// High impedance, `Z`
+    HighImpedance,
+}
+
+impl LogicState {
+    /// Arrow representation: 0, 1, 2 for `X` and 3 for `Z`
+    #[inline]
+    pub fn to_u8(self) -> u8 {
+        match self {
+            LogicState::Low => 0,
+            LogicState::High => 1,
+            LogicState::Unknown => 2,
+            LogicState::HighImpedance => 3,
+        }
+    }
+
+    #[inline]
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(LogicState::Low),
+            1 => Some(LogicState::High),
+            2 => Some(LogicState::Unknown),
+            3 => Some(LogicState::HighImpedance),
+            _ => None,
+        }
+    }
+
+    /// Parses `0`, `1`, `x`/`X` or `z`/`Z`
+    #[inline]
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            '0' => Some(LogicState::Low),
+            '1' => Some(LogicState::High),
+            'x' | 'X' => Some(LogicState::Unknown),
+            'z' | 'Z' => Some(LogicState::HighImpedance),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogicState::Low => "0",
+            LogicState::High => "1",
+            LogicState::Unknown => "X",
+            LogicState::HighImpedance => "Z",
+        }
+    }
+}
+
+impl From<bool> for LogicState {
+    #[inline]
+    fn from(value: bool) -> Self {
+        if value {
+            LogicState::High
+        } else {
+            LogicState::Low
+        }
+    }
+}
+
+/// Value of a single digital signal bit, drawn without needing an annotation context
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LogicBit(pub LogicState);
+
+impl<T: Into<LogicState>> From<T> for LogicBit {
+    fn from(v: T) -> Self {
+        Self(v.into())
+    }
+}
+
+impl re_types::SizeBytes for LogicBit {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        0
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        true
+    }
+}
+
+re_types::macros::impl_into_cow!(LogicBit);
+
+impl re_types::Loggable for LogicBit {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.components.LogicBit".into()
+    }
+
+    #[inline]
+    fn arrow_datatype() -> arrow2::datatypes::DataType {
+        arrow2::datatypes::DataType::UInt8
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        let data: Vec<Option<u8>> = data
+            .into_iter()
+            .map(|d| d.map(|d| d.into().0.to_u8()))
+            .collect();
+
+        Ok(arrow2::array::PrimitiveArray::<u8>::from(data).boxed())
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        let mismatch = || {
+            re_types::DeserializationError::datatype_mismatch(
+                Self::arrow_datatype(),
+                data.data_type().clone(),
+            )
+        };
+
+        let data = data
+            .as_any()
+            .downcast_ref::<arrow2::array::PrimitiveArray<u8>>()
+            .ok_or_else(mismatch)?;
+
+        // Values outside of the four states are read as unknown
+        Ok(data
+            .iter()
+            .map(|v| v.map(|v| LogicBit(LogicState::from_u8(*v).unwrap_or(LogicState::Unknown))))
+            .collect())
+    }
+}
+
+/// Event with a duration, starting at the time of its row. Its label and color are those of
+/// `class_id` in the annotation context
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EventSpan {
+    pub class_id: ClassId,
+    /// Duration in timeline units
+    pub duration: i64,
+}
+
+impl EventSpan {
+    #[inline]
+    pub fn new(class_id: impl Into<ClassId>, duration: i64) -> Self {
+        Self {
+            class_id: class_id.into(),
+            duration,
+        }
+    }
+
+    /// Span from `start` to `end`, logged at `start`
+    #[inline]
+    pub fn from_range(class_id: impl Into<ClassId>, start: i64, end: i64) -> Self {
+        Self::new(class_id, end.saturating_sub(start))
+    }
+}
+
+impl re_types::SizeBytes for EventSpan {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        0
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        true
+    }
+}
+
+re_types::macros::impl_into_cow!(EventSpan);
+
+impl re_types::Loggable for EventSpan {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.components.EventSpan".into()
+    }
+
+    #[inline]
+    fn arrow_datatype() -> arrow2::datatypes::DataType {
+        use arrow2::datatypes::{DataType, Field};
+        DataType::Struct(std::sync::Arc::new(vec![
+            Field::new("class_id", DataType::UInt16, false),
+            Field::new("duration", DataType::Int64, false),
+        ]))
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        use arrow2::{
+            array::{PrimitiveArray, StructArray},
+            bitmap::Bitmap,
+            datatypes::DataType,
+        };
+
+        let data: Vec<Option<EventSpan>> = data
+            .into_iter()
+            .map(|d| d.map(|d| d.into().into_owned()))
+            .collect();
+
+        let validity: Option<Bitmap> = data
+            .iter()
+            .any(Option::is_none)
+            .then(|| data.iter().map(Option::is_some).collect());
+
+        let class_ids = PrimitiveArray::new(
+            DataType::UInt16,
+            data.iter()
+                .map(|d| d.map_or(0, |d| d.class_id.0 .0))
+                .collect::<Vec<_>>()
+                .into(),
+            validity.clone(),
+        );
+        let durations = PrimitiveArray::new(
+            DataType::Int64,
+            data.iter()
+                .map(|d| d.map_or(0, |d| d.duration))
+                .collect::<Vec<_>>()
+                .into(),
+            validity.clone(),
+        );
+
+        Ok(StructArray::new(
+            Self::arrow_datatype(),
+            vec![class_ids.boxed(), durations.boxed()],
+            validity,
+        )
+        .boxed())
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        use arrow2::array::{PrimitiveArray, StructArray};
+
+        let mismatch = || {
+            re_types::DeserializationError::datatype_mismatch(
+                Self::arrow_datatype(),
+                data.data_type().clone(),
+            )
+        };
+
+        let data = data
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .ok_or_else(mismatch)?;
+
+        let [class_ids, durations] = data.values() else {
+            return Err(mismatch());
+        };
+        let class_ids = class_ids
+            .as_any()
+            .downcast_ref::<PrimitiveArray<u16>>()
+            .ok_or_else(mismatch)?;
+        let durations = durations
+            .as_any()
+            .downcast_ref::<PrimitiveArray<i64>>()
+            .ok_or_else(mismatch)?;
+
+        Ok((0..data.len())
+            .map(|i| {
+                data.is_valid(i)
+                    .then(|| EventSpan::new(class_ids.value(i), durations.value(i)))
+            })
+            .collect())
+    }
+}
+
+/// Discrete state given by its name, e.g. the state of a state machine. It is labelled with the
+/// name and colored from a hash of it, without needing an annotation context
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextState(pub re_types::datatypes::Utf8);
+
+impl re_types::SizeBytes for TextState {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        self.0.heap_size_bytes()
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        <re_types::datatypes::Utf8>::is_pod()
+    }
+}
+
+impl<T: Into<re_types::datatypes::Utf8>> From<T> for TextState {
+    fn from(v: T) -> Self {
+        Self(v.into())
+    }
+}
+
+re_types::macros::impl_into_cow!(TextState);
+
+impl re_types::Loggable for TextState {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.components.TextState".into()
+    }
+
+    #[allow(clippy::wildcard_imports)]
+    #[inline]
+    fn arrow_datatype() -> re_types::external::arrow2::datatypes::DataType {
+        re_types::datatypes::Utf8::arrow_datatype()
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        let data = data.into_iter().map(|d| d.map(|d| d.into().into_owned().0));
+        re_types::datatypes::Utf8::to_arrow_opt(data)
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        re_types::datatypes::Utf8::from_arrow_opt(data)
+            .map(|v| v.into_iter().map(|v| v.map(|v| TextState(v))).collect_vec())
+    }
+}
+
+/// Block of uniformly sampled scalars: the first sample is at the time of the row, the next ones
+/// `period` apart. Only the samples within the queried range are expanded by the view
+#[derive(Clone, Debug, PartialEq)]
+pub struct SampleBlock {
+    /// Time between samples in timeline units, positive
+    pub period: i64,
+    pub values: Vec<f64>,
+}
+
+impl SampleBlock {
+    #[inline]
+    pub fn new(period: i64, values: impl Into<Vec<f64>>) -> Self {
+        Self {
+            period,
+            values: values.into(),
+        }
+    }
+
+    /// Time of the sample at `index`, relative to the row's time
+    #[inline]
+    pub fn sample_offset(&self, index: usize) -> i64 {
+        self.period.saturating_mul(index as i64)
+    }
+
+    /// Indices of the samples within `[min, max]`, for a block starting at `start`
+    pub fn indices_within(&self, start: i64, min: i64, max: i64) -> std::ops::Range<usize> {
+        if self.period <= 0 || max < start {
+            return 0..0;
+        }
+
+        let first = (min.saturating_sub(start).max(0) as u64).div_ceil(self.period as u64);
+        let last = max.saturating_sub(start) as u64 / self.period as u64 + 1;
+        let len = self.values.len() as u64;
+        (first.min(len) as usize)..(last.min(len) as usize)
+    }
+
+    /// Indices of the samples within `[min, max]` and of the last one before `min`, which holds
+    /// at `min`, for a block starting at `start`
+    pub fn indices_from_previous(&self, start: i64, min: i64, max: i64) -> std::ops::Range<usize> {
+        if max < min {
+            return 0..0;
+        }
+
+        let within = self.indices_within(start, min, max);
+        within.start.saturating_sub(1)..within.end
+    }
+}
+
+impl re_types::SizeBytes for SampleBlock {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        self.values.heap_size_bytes()
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        false
+    }
+}
+
+re_types::macros::impl_into_cow!(SampleBlock);
+
+impl re_types::Loggable for SampleBlock {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.components.SampleBlock".into()
+    }
+
+    #[inline]
+    fn arrow_datatype() -> arrow2::datatypes::DataType {
+        use arrow2::datatypes::{DataType, Field};
+        DataType::Struct(std::sync::Arc::new(vec![
+            Field::new("period", DataType::Int64, false),
+            Field::new(
+                "values",
+                DataType::List(std::sync::Arc::new(Field::new(
+                    "item",
+                    DataType::Float64,
+                    false,
+                ))),
+                false,
+            ),
+        ]))
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        use arrow2::{
+            array::{ListArray, PrimitiveArray, StructArray},
+            bitmap::Bitmap,
+            datatypes::DataType,
+            offset::Offsets,
+        };
+
+        let data: Vec<Option<std::borrow::Cow<'a, Self>>> =
+            data.into_iter().map(|d| d.map(Into::into)).collect();
+
+        let validity: Option<Bitmap> = data
+            .iter()
+            .any(Option::is_none)
+            .then(|| data.iter().map(Option::is_some).collect());
+
+        let periods = PrimitiveArray::new(
+            DataType::Int64,
+            data.iter()
+                .map(|d| d.as_ref().map_or(0, |d| d.period))
+                .collect::<Vec<_>>()
+                .into(),
+            validity.clone(),
+        );
+
+        let offsets = Offsets::<i32>::try_from_lengths(
+            data.iter()
+                .map(|d| d.as_ref().map_or(0, |d| d.values.len())),
+        )?;
+        let values = PrimitiveArray::new(
+            DataType::Float64,
+            data.iter()
+                .flatten()
+                .flat_map(|d| d.values.iter().copied())
+                .collect::<Vec<_>>()
+                .into(),
+            None,
+        );
+        let DataType::Struct(fields) = Self::arrow_datatype() else {
+            unreachable!()
+        };
+        let values = ListArray::<i32>::new(
+            fields[1].data_type().clone(),
+            offsets.into(),
+            values.boxed(),
+            validity.clone(),
+        );
+
+        Ok(StructArray::new(
+            Self::arrow_datatype(),
+            vec![periods.boxed(), values.boxed()],
+            validity,
+        )
+        .boxed())
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        use arrow2::array::{ListArray, PrimitiveArray, StructArray};
+
+        let mismatch = || {
+            re_types::DeserializationError::datatype_mismatch(
+                Self::arrow_datatype(),
+                data.data_type().clone(),
+            )
+        };
+
+        let data = data
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .ok_or_else(mismatch)?;
+
+        let [periods, values] = data.values() else {
+            return Err(mismatch());
+        };
+        let periods = periods
+            .as_any()
+            .downcast_ref::<PrimitiveArray<i64>>()
+            .ok_or_else(mismatch)?;
+        let values = values
+            .as_any()
+            .downcast_ref::<ListArray<i32>>()
+            .ok_or_else(mismatch)?;
+        let samples = values
+            .values()
+            .as_any()
+            .downcast_ref::<PrimitiveArray<f64>>()
+            .ok_or_else(mismatch)?
+            .values();
+
+        Ok((0..data.len())
+            .map(|i| {
+                data.is_valid(i).then(|| {
+                    let (start, end) = values.offsets().start_end(i);
+                    SampleBlock::new(periods.value(i), &samples[start..end])
+                })
+            })
+            .collect())
+    }
+}
+
+/// Physical unit of the scalars of an entity, e.g. `V`. Values are shown with SI prefixes
+#[derive(Clone, Debug, PartialEq)]
+pub struct Unit(pub re_types::datatypes::Utf8);
+
+impl re_types::SizeBytes for Unit {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        self.0.heap_size_bytes()
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        <re_types::datatypes::Utf8>::is_pod()
+    }
+}
+
+impl<T: Into<re_types::datatypes::Utf8>> From<T> for Unit {
+    fn from(v: T) -> Self {
+        Self(v.into())
+    }
+}
+
+re_types::macros::impl_into_cow!(Unit);
+
+impl re_types::Loggable for Unit {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.components.Unit".into()
+    }
+
+    #[allow(clippy::wildcard_imports)]
+    #[inline]
+    fn arrow_datatype() -> re_types::external::arrow2::datatypes::DataType {
+        re_types::datatypes::Utf8::arrow_datatype()
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        let data = data.into_iter().map(|d| d.map(|d| d.into().into_owned().0));
+        re_types::datatypes::Utf8::to_arrow_opt(data)
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        re_types::datatypes::Utf8::from_arrow_opt(data)
+            .map(|v| v.into_iter().map(|v| v.map(|v| Unit(v))).collect_vec())
+    }
+}
+
+/// Calibration gain of the scalars of an entity, values are drawn as `raw * scale + offset`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Scale(pub f64);
+
+impl From<f64> for Scale {
+    fn from(v: f64) -> Self {
+        Self(v)
+    }
+}
+
+impl re_types::SizeBytes for Scale {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        0
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        true
+    }
+}
+
+re_types::macros::impl_into_cow!(Scale);
+
+impl re_types::Loggable for Scale {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.components.Scale".into()
+    }
+
+    #[inline]
+    fn arrow_datatype() -> arrow2::datatypes::DataType {
+        arrow2::datatypes::DataType::Float64
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        let data: Vec<Option<f64>> = data.into_iter().map(|d| d.map(|d| d.into().0)).collect();
+        Ok(arrow2::array::PrimitiveArray::<f64>::from(data).boxed())
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        let values = data
+            .as_any()
+            .downcast_ref::<arrow2::array::PrimitiveArray<f64>>()
+            .ok_or_else(|| {
+                re_types::DeserializationError::datatype_mismatch(
+                    Self::arrow_datatype(),
+                    data.data_type().clone(),
+                )
+            })?;
+
+        Ok(values.iter().map(|v| v.map(|v| Scale(*v))).collect())
+    }
+}
+
+/// Calibration offset of the scalars of an entity, see [`Scale`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Offset(pub f64);
+
+impl From<f64> for Offset {
+    fn from(v: f64) -> Self {
+        Self(v)
+    }
+}
+
+impl re_types::SizeBytes for Offset {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        0
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        true
+    }
+}
+
+re_types::macros::impl_into_cow!(Offset);
+
+impl re_types::Loggable for Offset {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.components.Offset".into()
+    }
+
+    #[inline]
+    fn arrow_datatype() -> arrow2::datatypes::DataType {
+        arrow2::datatypes::DataType::Float64
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        let data: Vec<Option<f64>> = data.into_iter().map(|d| d.map(|d| d.into().0)).collect();
+        Ok(arrow2::array::PrimitiveArray::<f64>::from(data).boxed())
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        let values = data
+            .as_any()
+            .downcast_ref::<arrow2::array::PrimitiveArray<f64>>()
+            .ok_or_else(|| {
+                re_types::DeserializationError::datatype_mismatch(
+                    Self::arrow_datatype(),
+                    data.data_type().clone(),
+                )
+            })?;
+
+        Ok(values.iter().map(|v| v.map(|v| Offset(*v))).collect())
+    }
+}
+
+/// How the scalars of an entity are joined, and looked up between samples
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Interpolation {
+    /// Straight lines between samples
+    #[default]
+    Linear,
+    /// Each sample holds until the next one, for registers and setpoints
+    PreviousHold,
+    /// Each sample holds until halfway to the next one
+    Nearest,
+    /// Samples only, nothing in between
+    Points,
+}
+
+impl Interpolation {
+    pub const ALL: [Interpolation; 4] = [
+        Interpolation::Linear,
+        Interpolation::PreviousHold,
+        Interpolation::Nearest,
+        Interpolation::Points,
+    ];
+
+    /// Arrow representation, the index in [`Self::ALL`]
+    #[inline]
+    pub fn to_u8(self) -> u8 {
+        match self {
+            Interpolation::Linear => 0,
+            Interpolation::PreviousHold => 1,
+            Interpolation::Nearest => 2,
+            Interpolation::Points => 3,
+        }
+    }
+
+    #[inline]
+    pub fn from_u8(value: u8) -> Option<Self> {
+        Self::ALL.get(value as usize).copied()
+    }
+}
+
+impl re_types::SizeBytes for Interpolation {
+    #[inline]
+    fn heap_size_bytes(&self) -> u64 {
+        0
+    }
+
+    #[inline]
+    fn is_pod() -> bool {
+        true
+    }
+}
+
+re_types::macros::impl_into_cow!(Interpolation);
+
+impl re_types::Loggable for Interpolation {
+    type Name = re_types::ComponentName;
+
+    #[inline]
+    fn name() -> Self::Name {
+        "wf.components.Interpolation".into()
+    }
+
+    #[inline]
+    fn arrow_datatype() -> arrow2::datatypes::DataType {
+        arrow2::datatypes::DataType::UInt8
+    }
+
+    fn to_arrow_opt<'a>(
+        data: impl IntoIterator<Item = Option<impl Into<std::borrow::Cow<'a, Self>>>>,
+    ) -> re_types::SerializationResult<Box<dyn arrow2::array::Array>>
+    where
+        Self: 'a,
+    {
+        let data: Vec<Option<u8>> = data
+            .into_iter()
+            .map(|d| d.map(|d| d.into().to_u8()))
+            .collect();
+
+        Ok(arrow2::array::PrimitiveArray::<u8>::from(data).boxed())
+    }
+
+    fn from_arrow_opt(
+        data: &dyn arrow2::array::Array,
+    ) -> re_types::DeserializationResult<Vec<Option<Self>>> {
+        let values = data
+            .as_any()
+            .downcast_ref::<arrow2::array::PrimitiveArray<u8>>()
+            .ok_or_else(|| {
+                re_types::DeserializationError::datatype_mismatch(
+                    Self::arrow_datatype(),
+                    data.data_type().clone(),
+                )
+            })?;
+
+        // Unknown modes fall back to linear
+        Ok(values
+            .iter()
+            .map(|v| v.map(|v| Interpolation::from_u8(*v).unwrap_or_default()))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_indices_within_range() {
+        // Samples at 100, 110, .., 190
+        let block = SampleBlock::new(10, vec![0.0; 10]);
+
+        assert_eq!(block.indices_within(100, 100, 190), 0..10);
+        assert_eq!(block.indices_within(100, 120, 150), 2..6);
+        // Bounds between samples only take the samples inside them
+        assert_eq!(block.indices_within(100, 121, 149), 3..5);
+        assert_eq!(block.indices_within(100, 0, 125), 0..3);
+        assert_eq!(block.indices_within(100, 185, 1000), 9..10);
+    }
+
+    #[test]
+    fn block_indices_outside_range_are_empty() {
+        let block = SampleBlock::new(10, vec![0.0; 10]);
+
+        assert!(block.indices_within(100, 0, 99).is_empty());
+        assert!(block.indices_within(100, 191, 300).is_empty());
+        assert!(block.indices_within(100, 121, 129).is_empty());
+        assert!(block.indices_within(100, 150, 120).is_empty());
+    }
+
+    #[test]
+    fn block_indices_from_previous_take_the_sample_before_the_range() {
+        let block = SampleBlock::new(10, vec![0.0; 10]);
+
+        assert_eq!(block.indices_from_previous(100, 120, 150), 1..6);
+        assert_eq!(block.indices_from_previous(100, 121, 129), 2..3);
+        // Blocks ending before the range still hold their last sample
+        assert_eq!(block.indices_from_previous(100, 300, 400), 9..10);
+        // Nothing is before a block starting within the range
+        assert_eq!(block.indices_from_previous(100, 50, 125), 0..3);
+        assert!(block.indices_from_previous(100, 0, 99).is_empty());
+        assert!(block.indices_from_previous(100, 400, 300).is_empty());
+    }
+
+    #[test]
+    fn block_indices_of_degenerate_blocks_are_empty() {
+        assert!(SampleBlock::new(0, vec![1.0; 4])
+            .indices_within(0, 0, 100)
+            .is_empty());
+        assert!(SampleBlock::new(-5, vec![1.0; 4])
+            .indices_within(0, -100, 100)
+            .is_empty());
+        assert!(SampleBlock::new(10, vec![])
+            .indices_within(0, 0, 100)
+            .is_empty());
+    }
+
+    #[test]
+    fn block_indices_saturate_at_the_time_limits() {
+        let block = SampleBlock::new(1, vec![0.0; 4]);
+
+        assert_eq!(block.indices_within(i64::MAX - 3, i64::MIN, i64::MAX), 0..4);
+        assert_eq!(block.indices_within(-10, i64::MIN, i64::MAX), 0..4);
+    }
+}
@@ -1,2 +1,3 @@
 pub mod archetypes;
+pub mod blueprint;
 pub mod components;
@@ -0,0 +1,236 @@
+use crate::WaveformSeries;
+
+/// SI prefixes from femto to tera, with the power of ten they stand for
+const SI_PREFIXES: [(i32, &str); 10] = [
+    (-15, "f"),
+    (-12, "p"),
+    (-9, "n"),
+    (-6, "µ"),
+    (-3, "m"),
+    (0, ""),
+    (3, "k"),
+    (6, "M"),
+    (9, "G"),
+    (12, "T"),
+];
+
+/// Formats `value` with `decimals` decimals, followed by `unit` with the SI prefix that keeps the
+/// value between 1 and 1000. Values without a unit are formatted as they are
+pub fn format_si(value: f64, unit: Option<&str>, decimals: usize) -> String {
+    let Some(unit) = unit.filter(|unit| !unit.is_empty()) else {
+        return format!("{value:.decimals$}");
+    };
+
+    let (mut exponent, mut prefix) = si_prefix(value);
+
+    // Values rounding up to 1000 are shown with the next prefix
+    let rounding = 10f64.powi(decimals as i32);
+    if value.is_finite()
+        && ((value / 10f64.powi(exponent)).abs() * rounding).round() >= 1000.0 * rounding
+    {
+        if let Some(&next) = SI_PREFIXES.iter().find(|(next, _)| *next > exponent) {
+            (exponent, prefix) = next;
+        }
+    }
+
+    let scaled = value / 10f64.powi(exponent);
+    format!("{scaled:.decimals$} {prefix}{unit}")
+}
+
+/// Power of ten and prefix to show `value` with
+pub fn si_prefix(value: f64) -> (i32, &'static str) {
+    if value == 0.0 || !value.is_finite() {
+        return (0, "");
+    }
+
+    let exponent = (value.abs().log10() / 3.0).floor() as i32 * 3;
+    SI_PREFIXES
+        .iter()
+        .rev()
+        .find(|(prefix_exponent, _)| *prefix_exponent <= exponent)
+        .copied()
+        .unwrap_or(SI_PREFIXES[0])
+}
+
+/// Formats a y-axis tick at `value`, `step` apart from the next one, with the SI prefix of the
+/// largest value in `range` so that all ticks of the axis share it
+pub fn format_tick(
+    value: f64,
+    step: f64,
+    range: &std::ops::RangeInclusive<f64>,
+    unit: Option<&str>,
+) -> String {
+    let unit = unit.filter(|unit| !unit.is_empty());
+    let (exponent, prefix) = match unit {
+        Some(_) => si_prefix(range.start().abs().max(range.end().abs())),
+        None => (0, ""),
+    };
+
+    let scale = 10f64.powi(exponent);
+    let scaled = value / scale;
+    let decimals = if step > 0.0 {
+        (-(step / scale).log10().floor()).max(0.0) as usize
+    } else {
+        0
+    };
+
+    match unit {
+        Some(unit) => format!("{scaled:.decimals$} {prefix}{unit}"),
+        None => format!("{scaled:.decimals$}"),
+    }
+}
+
+/// Y-axis of the series of a domain that share a unit
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnitAxis {
+    pub unit: Option<String>,
+
+    /// Range of the series when a domain mixes units, they are then drawn normalized to 0..1
+    /// so that each unit has its own axis
+    pub normalized: Option<(f64, f64)>,
+}
+
+impl UnitAxis {
+    /// Axes of the analog series of a domain, one per unit, normalized if there are several
+    pub fn of_domain<'a>(series: impl IntoIterator<Item = &'a WaveformSeries>) -> Vec<Self> {
+        let mut ranges: Vec<(Option<String>, (f64, f64))> = vec![];
+        for series in series {
+            let Some((min, max)) = series.analog_points.y_range else {
+                continue;
+            };
+
+            match ranges.iter_mut().find(|(unit, _)| *unit == series.unit) {
+                Some((_, range)) => *range = (range.0.min(min), range.1.max(max)),
+                None => ranges.push((series.unit.clone(), (min, max))),
+            }
+        }
+
+        if ranges.len() <= 1 {
+            return vec![UnitAxis {
+                unit: ranges.into_iter().next().and_then(|(unit, _)| unit),
+                normalized: None,
+            }];
+        }
+
+        ranges
+            .into_iter()
+            .map(|(unit, (min, max))| UnitAxis {
+                unit,
+                normalized: Some(if min < max {
+                    (min, max)
+                } else {
+                    (min - 0.5, max + 0.5)
+                }),
+            })
+            .collect()
+    }
+
+    /// Axis the series with `unit` are drawn on
+    pub fn find<'a>(axes: &'a [Self], unit: &Option<String>) -> Option<&'a Self> {
+        axes.iter().find(|axis| axis.unit == *unit)
+    }
+
+    /// Position at which `value` is drawn
+    pub fn normalize(&self, value: f64) -> f64 {
+        match self.normalized {
+            Some((min, max)) => (value - min) / (max - min),
+            None => value,
+        }
+    }
+
+    /// Value drawn at `y`
+    pub fn denormalize(&self, y: f64) -> f64 {
+        match self.normalized {
+            Some((min, max)) => min + y * (max - min),
+            None => y,
+        }
+    }
+
+    /// Tick label at `y`, `step` apart from the next one, on an axis spanning `range`
+    pub fn format_tick(&self, y: f64, step: f64, range: &std::ops::RangeInclusive<f64>) -> String {
+        let scale = match self.normalized {
+            Some((min, max)) => max - min,
+            None => 1.0,
+        };
+        let range = self.denormalize(*range.start())..=self.denormalize(*range.end());
+        format_tick(
+            self.denormalize(y),
+            step * scale,
+            &range,
+            self.unit.as_deref(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefixes_keep_values_between_1_and_1000() {
+        assert_eq!(si_prefix(1.0), (0, ""));
+        assert_eq!(si_prefix(999.0), (0, ""));
+        assert_eq!(si_prefix(1500.0), (3, "k"));
+        assert_eq!(si_prefix(-0.002), (-3, "m"));
+        assert_eq!(si_prefix(4.7e-9), (-9, "n"));
+    }
+
+    #[test]
+    fn zero_and_non_finite_values_have_no_prefix() {
+        assert_eq!(si_prefix(0.0), (0, ""));
+        assert_eq!(si_prefix(f64::NAN), (0, ""));
+        assert_eq!(si_prefix(f64::INFINITY), (0, ""));
+        assert_eq!(si_prefix(f64::NEG_INFINITY), (0, ""));
+
+        assert_eq!(format_si(0.0, Some("V"), 3), "0.000 V");
+        assert_eq!(format_si(f64::NAN, Some("V"), 1), "NaN V");
+        assert_eq!(format_si(f64::INFINITY, Some("V"), 1), "inf V");
+    }
+
+    #[test]
+    fn values_beyond_the_prefixes_use_the_outermost_ones() {
+        assert_eq!(si_prefix(5e15), (12, "T"));
+        assert_eq!(si_prefix(1e-18), (-15, "f"));
+
+        assert_eq!(format_si(5e15, Some("Hz"), 0), "5000 THz");
+        assert_eq!(format_si(2e-18, Some("A"), 3), "0.002 fA");
+    }
+
+    #[test]
+    fn values_rounding_up_to_1000_take_the_next_prefix() {
+        assert_eq!(format_si(999.9996, Some("V"), 3), "1.000 kV");
+        assert_eq!(format_si(-0.9999996, Some("V"), 3), "-1.000 V");
+        assert_eq!(format_si(999.4, Some("V"), 0), "999 V");
+        // There is no prefix beyond tera
+        assert_eq!(format_si(999.9996e12, Some("V"), 3), "1000.000 TV");
+    }
+
+    #[test]
+    fn values_without_unit_have_no_prefix() {
+        assert_eq!(format_si(1500.0, Some("V"), 2), "1.50 kV");
+        assert_eq!(format_si(1234.5678, None, 2), "1234.57");
+        assert_eq!(format_si(1234.5678, Some(""), 2), "1234.57");
+    }
+
+    #[test]
+    fn ticks_share_the_prefix_of_the_axis() {
+        let range = 0.0..=2000.0;
+        assert_eq!(format_tick(1500.0, 500.0, &range, Some("V")), "1.5 kV");
+        assert_eq!(format_tick(0.0, 500.0, &range, Some("V")), "0.0 kV");
+        assert_eq!(format_tick(1500.0, 500.0, &range, None), "1500");
+        assert_eq!(format_tick(0.25, 0.05, &(-1.0..=1.0), Some("A")), "0.25 A");
+    }
+
+    #[test]
+    fn ticks_of_degenerate_axes_have_no_prefix() {
+        assert_eq!(format_tick(0.0, 0.0, &(0.0..=0.0), Some("V")), "0 V");
+        assert_eq!(
+            format_tick(2.0, 1.0, &(f64::NAN..=f64::NAN), Some("V")),
+            "2 V"
+        );
+        assert_eq!(
+            format_tick(2.0, 1.0, &(0.0..=f64::INFINITY), Some("V")),
+            "2 V"
+        );
+    }
+}
@@ -0,0 +1,434 @@
+use std::collections::{BTreeSet, HashMap};
+
+use re_log_types::{EntityPath, EntityPathFilter, EntityPathSubs};
+use re_space_view::view_property::{ViewProperty, ViewPropertyQueryError};
+use re_viewer_context::{SpaceViewId, ViewerContext};
+
+use crate::{
+    space_view_class::SelectedMode,
+    types::blueprint::{
+        archetypes::{WaveformGrouping, WaveformViewState},
+        components::{
+            BusRadix, DomainHeight, DomainOrder, GroupingDepth, LaneRule, SecondMarker,
+            SelectedEntity, SidePanelFraction, YAxisDomain,
+        },
+    },
+    WaveformDomain, WaveformTime,
+};
+
+/// Default fraction of the view width taken by the side panel
+pub const DEFAULT_SIDE_PANEL_FRACTION: f32 = 0.3;
+
+/// Sizes of the side panel and domain rows
+#[derive(Clone, Debug, PartialEq)]
+pub struct ViewLayout {
+    /// Fraction of the view width taken by the side panel
+    pub side_panel_fraction: f32,
+
+    /// Relative height of each domain, domains not listed have a height of 1
+    pub domain_heights: HashMap<WaveformDomain, f32>,
+}
+
+impl ViewLayout {
+    pub fn domain_height(&self, domain: &WaveformDomain) -> f32 {
+        self.domain_heights.get(domain).copied().unwrap_or(1.0)
+    }
+}
+
+/// Radix bus values are labelled in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Radix {
+    Binary,
+    Octal,
+    Decimal,
+    #[default]
+    Hexadecimal,
+}
+
+impl Radix {
+    pub const ALL: [Radix; 4] = [
+        Radix::Binary,
+        Radix::Octal,
+        Radix::Decimal,
+        Radix::Hexadecimal,
+    ];
+
+    pub fn base(self) -> u32 {
+        match self {
+            Radix::Binary => 2,
+            Radix::Octal => 8,
+            Radix::Decimal => 10,
+            Radix::Hexadecimal => 16,
+        }
+    }
+
+    pub fn from_base(base: u32) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|radix| radix.base() == base)
+            .unwrap_or_default()
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Radix::Binary => "Binary",
+            Radix::Octal => "Octal",
+            Radix::Decimal => "Decimal",
+            Radix::Hexadecimal => "Hexadecimal",
+        }
+    }
+
+    /// Formats the low `width` bits of `value`, padded with zeros to the digits of the bus width
+    pub fn format(self, value: u64, width: u8) -> String {
+        let width = u32::from(width.clamp(1, 64));
+        let value = if width == 64 {
+            value
+        } else {
+            value & ((1 << width) - 1)
+        };
+
+        match self {
+            Radix::Binary => format!("{value:0digits$b}", digits = width as usize),
+            Radix::Octal => format!("{value:0digits$o}", digits = width.div_ceil(3) as usize),
+            Radix::Decimal => value.to_string(),
+            Radix::Hexadecimal => format!("{value:0digits$X}", digits = width.div_ceil(4) as usize),
+        }
+    }
+}
+
+/// View state kept in the blueprint as the [`WaveformViewState`] view property, so it is saved
+/// with the layout and can be set from the SDK
+#[derive(Clone, Debug, PartialEq)]
+pub struct PersistedViewState {
+    /// Secondary marker position on the timeline
+    pub second_marker: Option<WaveformTime>,
+
+    /// Domains from top to bottom
+    pub domain_order: Vec<WaveformDomain>,
+
+    /// Selected mode- displays only entities that are selected
+    pub selected_mode: SelectedMode,
+
+    /// Sizes of the side panel and domain rows
+    pub layout: ViewLayout,
+
+    /// Radix bus values are labelled in
+    pub bus_radix: Radix,
+
+    /// Domains drawn with a y-axis
+    pub y_axis_domains: BTreeSet<WaveformDomain>,
+}
+
+impl PersistedViewState {
+    pub fn property(ctx: &ViewerContext<'_>, space_view_id: SpaceViewId) -> ViewProperty {
+        ViewProperty::from_archetype::<WaveformViewState>(
+            ctx.blueprint_db(),
+            ctx.blueprint_query,
+            space_view_id,
+        )
+    }
+
+    pub fn load(property: &ViewProperty) -> Result<Self, ViewPropertyQueryError> {
+        let second_marker = property
+            .component_or_empty::<SecondMarker>()?
+            .map(|marker| marker.0 .0);
+
+        let domain_order: Vec<WaveformDomain> = property
+            .component_array_or_empty::<DomainOrder>()?
+            .into_iter()
+            .map(|domain| domain.0.to_string())
+            .collect();
+
+        let domain_heights = domain_order
+            .iter()
+            .cloned()
+            .zip(
+                property
+                    .component_array_or_empty::<DomainHeight>()?
+                    .into_iter()
+                    .map(|height| height.0 .0),
+            )
+            .collect();
+
+        let bus_radix = property
+            .component_or_empty::<BusRadix>()?
+            .map_or_else(Radix::default, |radix| Radix::from_base(radix.0 .0));
+
+        let y_axis_domains = property
+            .component_array_or_empty::<YAxisDomain>()?
+            .into_iter()
+            .map(|domain| domain.0.to_string())
+            .collect();
+
+        let side_panel_fraction = property
+            .component_or_empty::<SidePanelFraction>()?
+            .map_or(DEFAULT_SIDE_PANEL_FRACTION, |fraction| fraction.0 .0);
+
+        let selected_entities = property
+            .component_array_or_empty::<SelectedEntity>()?
+            .into_iter()
+            .map(|entity| EntityPath::parse_forgiving(entity.0.as_str()))
+            .collect();
+
+        Ok(Self {
+            second_marker,
+            domain_order,
+            selected_mode: SelectedMode::from_paths(selected_entities),
+            layout: ViewLayout {
+                side_panel_fraction,
+                domain_heights,
+            },
+            bus_radix,
+            y_axis_domains,
+        })
+    }
+
+    /// Writes the components that differ from `loaded` to the blueprint
+    pub fn save(&self, loaded: &Self, ctx: &ViewerContext<'_>, property: &ViewProperty) {
+        if self.second_marker != loaded.second_marker {
+            let second_marker: Vec<SecondMarker> =
+                self.second_marker.into_iter().map(Into::into).collect();
+            property.save_blueprint_component(ctx, &second_marker);
+        }
+
+        if self.domain_order != loaded.domain_order {
+            let domain_order: Vec<DomainOrder> = self
+                .domain_order
+                .iter()
+                .map(|domain| domain.as_str().into())
+                .collect();
+            property.save_blueprint_component(ctx, &domain_order);
+        }
+
+        if self.selected_mode != loaded.selected_mode {
+            let selected_entities: Vec<SelectedEntity> = match &self.selected_mode {
+                SelectedMode::Selected(paths) => {
+                    let mut paths: Vec<_> = paths.iter().map(|p| p.to_string()).collect();
+                    paths.sort();
+                    paths.into_iter().map(Into::into).collect()
+                }
+                SelectedMode::Unselected => vec![],
+            };
+            property.save_blueprint_component(ctx, &selected_entities);
+        }
+
+        if self.bus_radix != loaded.bus_radix {
+            property.save_blueprint_component(ctx, &BusRadix::from(self.bus_radix.base()));
+        }
+
+        if self.y_axis_domains != loaded.y_axis_domains {
+            let y_axis_domains: Vec<YAxisDomain> = self
+                .y_axis_domains
+                .iter()
+                .map(|domain| domain.as_str().into())
+                .collect();
+            property.save_blueprint_component(ctx, &y_axis_domains);
+        }
+
+        if self.layout.side_panel_fraction != loaded.layout.side_panel_fraction {
+            property.save_blueprint_component(
+                ctx,
+                &SidePanelFraction::from(self.layout.side_panel_fraction),
+            );
+        }
+
+        // Heights are stored in domain order, so they follow it when it changes
+        if self.layout.domain_heights != loaded.layout.domain_heights
+            || (self.domain_order != loaded.domain_order && !self.layout.domain_heights.is_empty())
+        {
+            let domain_heights: Vec<DomainHeight> = self
+                .domain_order
+                .iter()
+                .map(|domain| self.layout.domain_height(domain).into())
+                .collect();
+            property.save_blueprint_component(ctx, &domain_heights);
+        }
+    }
+
+    /// Moves `domain` next to `target`, before it unless `after`
+    pub fn move_domain(&mut self, domain: &WaveformDomain, target: &WaveformDomain, after: bool) {
+        if domain == target {
+            return;
+        }
+
+        let Some(from) = self.domain_order.iter().position(|d| d == domain) else {
+            return;
+        };
+        let domain = self.domain_order.remove(from);
+
+        match self.domain_order.iter().position(|d| d == target) {
+            Some(to) => self
+                .domain_order
+                .insert(if after { to + 1 } else { to }, domain),
+            None => self.domain_order.insert(from, domain),
+        }
+    }
+
+    /// Appends domains not in the order yet, and returns the position of every domain
+    pub fn domain_index<'a>(
+        &mut self,
+        domains: impl IntoIterator<Item = &'a WaveformDomain>,
+    ) -> HashMap<WaveformDomain, usize> {
+        for domain in domains {
+            if !self.domain_order.contains(domain) {
+                self.domain_order.push(domain.clone());
+            }
+        }
+
+        self.domain_order
+            .iter()
+            .enumerate()
+            .map(|(i, domain)| (domain.clone(), i))
+            .collect()
+    }
+}
+
+/// Grouping of entities into domains, from the [`WaveformGrouping`] view property
+#[derive(Clone, Debug)]
+pub struct DomainGrouping {
+    /// Number of leading entity path parts that name a domain
+    pub depth: usize,
+
+    /// Rules as written in the blueprint, `name = filter`
+    pub lane_rules: Vec<String>,
+
+    /// Named lanes and their parsed filters
+    lanes: Vec<(WaveformDomain, EntityPathFilter)>,
+}
+
+impl DomainGrouping {
+    pub fn property(ctx: &ViewerContext<'_>, space_view_id: SpaceViewId) -> ViewProperty {
+        ViewProperty::from_archetype::<WaveformGrouping>(
+            ctx.blueprint_db(),
+            ctx.blueprint_query,
+            space_view_id,
+        )
+    }
+
+    pub fn load(
+        property: &ViewProperty,
+        space_origin: &EntityPath,
+    ) -> Result<Self, ViewPropertyQueryError> {
+        let depth = property
+            .component_or_empty::<GroupingDepth>()?
+            .map_or(1, |depth| depth.0 .0 as usize)
+            .max(1);
+
+        let lane_rules: Vec<String> = property
+            .component_array_or_empty::<LaneRule>()?
+            .into_iter()
+            .map(|rule| rule.0.to_string())
+            .collect();
+
+        let subst_env = EntityPathSubs::new_with_origin(space_origin);
+        let lanes = lane_rules
+            .iter()
+            .filter_map(|rule| {
+                let (name, filter) = rule.split_once('=')?;
+                let filter = filter
+                    .split(',')
+                    .map(str::trim)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                Some((
+                    name.trim().to_owned(),
+                    EntityPathFilter::parse_forgiving(&filter, &subst_env),
+                ))
+            })
+            .collect();
+
+        Ok(Self {
+            depth,
+            lane_rules,
+            lanes,
+        })
+    }
+
+    /// Domain of `entity_path`, the first lane whose filter matches it, or its first `depth`
+    /// path parts
+    pub fn domain(&self, entity_path: &EntityPath) -> Option<WaveformDomain> {
+        if let Some((name, _)) = self
+            .lanes
+            .iter()
+            .find(|(_, filter)| filter.matches(entity_path))
+        {
+            return Some(name.clone());
+        }
+
+        if entity_path.is_root() {
+            return None;
+        }
+
+        // Named like the first path parts, without a leading `/`, e.g. `A` or `A/B`
+        let parts = entity_path.as_slice();
+        Some(
+            parts[..self.depth.min(parts.len())]
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("/"),
+        )
+    }
+
+    /// Writes a changed depth or changed rules to the blueprint
+    pub fn save(
+        &self,
+        depth: usize,
+        lane_rules: &[String],
+        ctx: &ViewerContext<'_>,
+        property: &ViewProperty,
+    ) {
+        if depth != self.depth {
+            property.save_blueprint_component(ctx, &GroupingDepth::from(depth as u32));
+        }
+
+        if lane_rules != self.lane_rules.as_slice() {
+            let lane_rules: Vec<LaneRule> =
+                lane_rules.iter().map(|rule| rule.as_str().into()).collect();
+            property.save_blueprint_component(ctx, &lane_rules);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn radix_pads_to_the_bus_width() {
+        assert_eq!(Radix::Binary.format(0b101, 4), "0101");
+        assert_eq!(Radix::Octal.format(0o7, 7), "007");
+        assert_eq!(Radix::Decimal.format(42, 8), "42");
+        assert_eq!(Radix::Hexadecimal.format(0xA, 9), "00A");
+    }
+
+    #[test]
+    fn radix_masks_bits_above_the_width() {
+        assert_eq!(Radix::Binary.format(0xFF, 3), "111");
+        assert_eq!(Radix::Decimal.format(0x1FF, 8), "255");
+        assert_eq!(Radix::Hexadecimal.format(0xABCD, 8), "CD");
+    }
+
+    #[test]
+    fn radix_formats_full_64_bit_values() {
+        assert_eq!(Radix::Hexadecimal.format(u64::MAX, 64), "FFFFFFFFFFFFFFFF");
+        assert_eq!(Radix::Decimal.format(u64::MAX, 64), u64::MAX.to_string());
+        assert_eq!(Radix::Octal.format(u64::MAX, 64), format!("{:o}", u64::MAX));
+        assert_eq!(Radix::Binary.format(1, 64), format!("{:064b}", 1));
+    }
+
+    #[test]
+    fn radix_treats_width_0_as_a_single_bit() {
+        assert_eq!(Radix::Binary.format(0b10, 0), "0");
+        assert_eq!(Radix::Hexadecimal.format(0xF, 0), "1");
+        assert_eq!(Radix::Decimal.format(3, 0), "1");
+    }
+
+    #[test]
+    fn radix_round_trips_through_its_base() {
+        for radix in Radix::ALL {
+            assert_eq!(Radix::from_base(radix.base()), radix);
+        }
+        assert_eq!(Radix::from_base(3), Radix::default());
+    }
+}
@@ -1,28 +1,54 @@
-use re_log_types::ResolvedTimeRange;
+use re_data_store::LatestAtQuery;
+use re_log_types::{ResolvedTimeRange, TimeInt, Timeline};
 use re_query::{PromiseResult, QueryError};
-use re_sdk::Loggable;
+use re_sdk::{EntityPath, Loggable};
 use re_space_view::{range_with_blueprint_resolved_data, RangeResultsExt};
 use re_types::{
     components::ClassId,
     datatypes::{AnnotationInfo, TimeRange, Utf8},
+    ComponentName,
 };
 use re_viewer_context::{
-    auto_color_egui, auto_color_for_entity_path, IdentifiedViewSystem, VisualizerQueryInfo,
-    VisualizerSystem,
+    auto_color_egui, IdentifiedViewSystem, VisualizerQueryInfo, VisualizerSystem,
 };
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, sync::Arc};
 
 use crate::{
-    annotation_context::AnnotationWaveformContext, AnalogPoint, AnalogPoints, DiscreteTransition,
-    DiscreteTransitionKind, EventMarker,
+    annotation_context::AnnotationWaveformContext,
+    diagnostics::{EntityDiagnostics, RowIssue},
+    search::{self, SearchRequest},
+    series_cache::{channel_color, SeriesKey, WaveformSeriesCache},
+    view_state::DomainGrouping,
+    DiscreteTransition, DiscreteTransitionKind, EventMarker,
 };
 
-use super::{types::archetypes::WaveformPoint, WaveformDomain, WaveformEvents, WaveformSeries};
+use super::{
+    types::{
+        archetypes::WaveformPoint,
+        components::{Interpolation, LogicState},
+    },
+    WaveformDomain, WaveformEvents, WaveformSeries, WaveformTime,
+};
+
+/// Fraction of the visible window width that is additionally queried on either side, so that
+/// panning doesn't immediately run into unloaded data
+const QUERY_WINDOW_MARGIN_PC: f64 = 0.5;
+
+/// Visible window widths a search scans past its start each frame. Searches without a match
+/// within them carry on from there the next frame, so that long histories don't stall the view
+const SEARCH_WINDOWS_PER_FRAME: WaveformTime = 16;
 
 #[derive(Default, Debug)]
 pub struct WaveformSystem {
-    pub all_series: BTreeMap<WaveformDomain, Vec<WaveformSeries>>,
+    pub all_series: BTreeMap<WaveformDomain, Vec<Arc<WaveformSeries>>>,
     pub all_events: WaveformEvents,
+    /// Time extent of the data of the visible entities within their visible history, regardless
+    /// of the window that was queried
+    pub time_extent: Option<(WaveformTime, WaveformTime)>,
+    /// Warnings for entities whose rows aren't all drawn as logged
+    pub diagnostics: BTreeMap<EntityPath, EntityDiagnostics>,
+    /// Match of the search the space view posted last frame, if any
+    pub search_match: Option<WaveformTime>,
 }
 
 impl IdentifiedViewSystem for WaveformSystem {
@@ -43,7 +69,12 @@ impl VisualizerSystem for WaveformSystem {
         context_systems: &re_viewer_context::ViewContextCollection,
     ) -> Result<Vec<re_renderer::QueueableDrawData>, re_viewer_context::SpaceViewSystemExecutionError>
     {
-        match self.load_points(ctx, query, context_systems) {
+        let grouping = DomainGrouping::load(
+            &DomainGrouping::property(ctx.viewer_ctx, query.space_view_id),
+            query.space_origin,
+        )?;
+
+        match self.load_points(ctx, query, context_systems, &grouping) {
             Ok(_) | Err(QueryError::PrimaryNotFound(_)) => Ok(vec![]),
             Err(e) => Err(e.into()),
         }
@@ -66,313 +97,1011 @@ impl WaveformSystem {
         ctx: &re_viewer_context::ViewContext<'_>,
         query: &re_viewer_context::ViewQuery<'_>,
         context_systems: &re_viewer_context::ViewContextCollection,
+        grouping: &DomainGrouping,
     ) -> Result<(), QueryError> {
-        use crate::types::components::*;
-
         self.all_series = Default::default();
         self.all_events = Default::default();
+        self.time_extent = None;
+        self.diagnostics = Default::default();
+        self.search_match = None;
+
+        // Window the plots showed last frame, as stored by the space view
+        let visible_window = ctx.viewer_ctx.egui_ctx.data(|d| {
+            d.get_temp::<(WaveformTime, WaveformTime)>(crate::visible_window_id(
+                query.space_view_id,
+                query.timeline.name().as_str(),
+            ))
+        });
+
+        // Search posted by the space view, run over the store rather than the loaded rows
+        let search_request = ctx.viewer_ctx.egui_ctx.data_mut(|d| {
+            d.remove_temp::<SearchRequest>(crate::search_request_id(
+                query.space_view_id,
+                query.timeline.name().as_str(),
+            ))
+        });
+
+        // Entities the search runs over once all are loaded, with their rows' time extent
+        let mut searched = vec![];
 
         let annotation_map = context_systems
             .get::<AnnotationWaveformContext>()
             .map_err(|e| QueryError::BadAccess)?;
 
-        let resolver = ctx.recording().resolver();
+        ctx.viewer_ctx.cache.entry(
+            |cache: &mut WaveformSeriesCache| -> Result<(), QueryError> {
+                cache.apply_store_changes(ctx.recording().store_id());
 
-        let mut series_iter = query.iter_visible_data_results(ctx, Self::identifier());
+                for series_result in query.iter_visible_data_results(ctx, Self::identifier()) {
+                    let entity_path = series_result.entity_path.clone();
 
-        series_iter.try_for_each(|series_result| -> Result<(), QueryError> {
-            let entity_path = series_result.entity_path.clone();
+                    let Some(domain) = grouping.domain(&entity_path) else {
+                        continue;
+                    };
 
-            let Some(domain) = entity_path.iter().next() else {
-                return Ok(());
-            };
+                    let history_range =
+                        visible_history_range(series_result.query_range(), query.latest_at);
+                    let window_range = clamp_to_window(history_range, visible_window);
 
-            let color = auto_color_for_entity_path(&entity_path);
-
-            let mut min_time = None;
-            let mut max_time = None;
-
-            let mut series = WaveformSeries {
-                entity_path: entity_path.clone(),
-                min_time: i64::MAX,
-                max_time: i64::MIN,
-                analog_points: AnalogPoints {
-                    points: BTreeMap::new(),
-                    y_range: None,
-                },
-                discrete_points: Default::default(),
-                color: color.into(),
-            };
+                    let loader = RowLoader {
+                        ctx,
+                        timeline: query.timeline,
+                        data_result: series_result,
+                        annotation_map,
+                        history_range,
+                    };
+
+                    let extent = loader.time_extent();
+                    if let Some((extent_min, extent_max)) = extent {
+                        self.time_extent = Some(match self.time_extent {
+                            Some((min, max)) => (min.min(extent_min), max.max(extent_max)),
+                            None => (extent_min, extent_max),
+                        });
+                    }
+
+                    let cached = cache.entry(SeriesKey {
+                        space_view_id: query.space_view_id,
+                        entity_path: entity_path.clone(),
+                        timeline: query.timeline,
+                    });
+                    cached.update(&loader, window_range)?;
+
+                    for (time, event_markers) in cached.events() {
+                        for event_marker in event_markers {
+                            self.all_events.push(*time, event_marker.clone());
+                        }
+                    }
+
+                    self.all_series
+                        .entry(domain)
+                        .or_default()
+                        .extend(cached.series());
+
+                    let diagnostics = cached.diagnostics();
+                    if !diagnostics.is_empty() {
+                        self.diagnostics.insert(entity_path.clone(), diagnostics);
+                    }
+
+                    // Entities without any of the components the condition looks at are skipped
+                    if let Some((request, extent)) = search_request.as_ref().zip(extent) {
+                        if (request.targets.is_empty() || request.targets.contains(&entity_path))
+                            && request.query.condition().is_some_and(|condition| {
+                                loader.has_any_component(&condition.component_names())
+                            })
+                        {
+                            let block_width = window_range
+                                .max()
+                                .as_i64()
+                                .saturating_sub(window_range.min().as_i64());
+                            searched.push((loader, block_width, extent));
+                        }
+                    }
+                }
+
+                Ok(())
+            },
+        )?;
 
-            let query_range = series_result.query_range();
+        if let Some(request) = search_request {
+            self.search(ctx, query, request, visible_window, &searched)?;
+        }
+
+        Ok(())
+    }
+
+    /// Runs `request` over the `searched` entities, as far as [`SEARCH_WINDOWS_PER_FRAME`] widths
+    /// of the visible window past its start. Without a match there, the rest of the search is
+    /// posted again for the next frame
+    fn search(
+        &mut self,
+        ctx: &re_viewer_context::ViewContext<'_>,
+        query: &re_viewer_context::ViewQuery<'_>,
+        request: SearchRequest,
+        visible_window: Option<(WaveformTime, WaveformTime)>,
+        searched: &[(RowLoader<'_>, WaveformTime, (WaveformTime, WaveformTime))],
+    ) -> Result<(), QueryError> {
+        let Some((extent_min, extent_max)) = searched
+            .iter()
+            .map(|(_, _, extent)| *extent)
+            .reduce(|(a_min, a_max), (b_min, b_max)| (a_min.min(b_min), a_max.max(b_max)))
+        else {
+            return Ok(());
+        };
+
+        // The span is scanned from the first row of the entities at the earliest, and without a
+        // window yet, there is no limit
+        let limit = visible_window.map(|(min, max)| {
+            let span = (max - min).max(1).saturating_mul(SEARCH_WINDOWS_PER_FRAME);
+            if request.forward {
+                request.from.max(extent_min).saturating_add(span)
+            } else {
+                request.from.min(extent_max).saturating_sub(span)
+            }
+        });
+
+        // Entities are searched no further than the match found so far
+        for (loader, block_width, _) in searched {
+            let entity_path = &loader.data_result.entity_path;
+            let limit = self.search_match.or(limit);
+            if let Some(time) =
+                search::find_in_store(loader, entity_path, &request, *block_width, limit)?
+            {
+                self.search_match = Some(time);
+            }
+        }
 
-            //TODO: Limit time range and plotted elements queried to the view- this returns nothing!
-            let time_range = ResolvedTimeRange::from_relative_time_range(
-                &match query_range {
-                    re_viewer_context::QueryRange::TimeRange(range) => range.clone(),
-                    re_viewer_context::QueryRange::LatestAt => TimeRange {
-                        start: re_types::datatypes::TimeRangeBoundary::AT_CURSOR,
-                        end: re_types::datatypes::TimeRangeBoundary::AT_CURSOR,
+        // Without a match within the span, the search carries on from its end the next frame
+        let continues = |limit: &WaveformTime| {
+            if request.forward {
+                *limit < extent_max
+            } else {
+                *limit > extent_min
+            }
+        };
+        if let Some(limit) = limit.filter(|limit| self.search_match.is_none() && continues(limit)) {
+            let egui_ctx = &ctx.viewer_ctx.egui_ctx;
+            let id = crate::search_request_id(query.space_view_id, query.timeline.name().as_str());
+            egui_ctx.data_mut(|d| {
+                d.insert_temp(
+                    id,
+                    SearchRequest {
+                        from: limit,
+                        ..request
                     },
-                },
-                query.latest_at,
+                );
+            });
+            egui_ctx.request_repaint();
+        }
+
+        Ok(())
+    }
+}
+
+/// Queries the rows of one entity for its cached series
+pub struct RowLoader<'a> {
+    ctx: &'a re_viewer_context::ViewContext<'a>,
+    timeline: Timeline,
+    data_result: &'a re_viewer_context::DataResult,
+    annotation_map: &'a AnnotationWaveformContext,
+    history_range: ResolvedTimeRange,
+}
+
+impl<'a> RowLoader<'a> {
+    /// Visible history of the entity, rows outside of it are never loaded
+    pub fn history_range(&self) -> ResolvedTimeRange {
+        self.history_range
+    }
+
+    /// Whether the entity has any of `component_names` on the timeline
+    pub fn has_any_component(&self, component_names: &[ComponentName]) -> bool {
+        self.ctx
+            .recording()
+            .store()
+            .all_components(&self.timeline, &self.data_result.entity_path)
+            .is_some_and(|all| component_names.iter().any(|name| all.contains(name)))
+    }
+
+    /// Times of the first and last row of the entity within its visible history, whether loaded
+    /// or not
+    pub fn time_extent(&self) -> Option<(WaveformTime, WaveformTime)> {
+        use re_types::Archetype as _;
+
+        let recording = self.ctx.recording();
+        let entity_path = &self.data_result.entity_path;
+
+        let first = recording
+            .store()
+            .entity_min_time(&self.timeline, entity_path)?
+            .max(self.history_range.min());
+
+        let last = recording
+            .query_caches()
+            .latest_at(
+                recording.store(),
+                &LatestAtQuery::new(self.timeline, self.history_range.max()),
+                entity_path,
+                WaveformPoint::all_components().iter().copied(),
+            )
+            .components
+            .values()
+            .map(|component| component.index().0)
+            .filter(|time| !time.is_static())
+            .max()?;
+
+        (first <= last).then(|| (first.as_i64(), last.as_i64()))
+    }
+
+    /// Queries the rows within `range`, with one value per instance.
+    ///
+    /// With `extend_to_previous`, analog and discrete values are queried from the row in effect
+    /// at the range start, as they hold until their next row.
+    pub fn load(
+        &self,
+        range: ResolvedTimeRange,
+        extend_to_previous: bool,
+    ) -> Result<LoadedRows, QueryError> {
+        use crate::types::components::*;
+
+        let ctx = self.ctx;
+        let annotation_map = self.annotation_map;
+        let series_result = self.data_result;
+        let entity_path = &series_result.entity_path;
+        let resolver = ctx.recording().resolver();
+
+        let mut rows = LoadedRows::default();
+
+        let range_query = |component_name: ComponentName| {
+            let range = if extend_to_previous {
+                extend_to_previous_row(
+                    ctx,
+                    self.timeline,
+                    entity_path,
+                    range,
+                    self.history_range,
+                    [component_name],
+                )
+            } else {
+                range
+            };
+            re_data_store::RangeQuery::new(self.timeline, range)
+        };
+
+        let scalar_points_result = range_with_blueprint_resolved_data(
+            ctx,
+            None,
+            &range_query(Scalar::name()),
+            series_result,
+            [Scalar::name()],
+        );
+
+        if let Some(all_scalars) =
+            scalar_points_result.get_required_component_dense::<Scalar>(resolver)
+        {
+            let all_scalars = all_scalars?;
+
+            let entry_range = all_scalars.entry_range();
+
+            rows.pending |= !matches!(
+                all_scalars.status(),
+                (PromiseResult::Ready(()), PromiseResult::Ready(()))
             );
 
-            let time_range = ResolvedTimeRange::EVERYTHING;
+            rows.scalars = all_scalars
+                .range_indices(entry_range.clone())
+                .zip(all_scalars.range_data(entry_range))
+                .filter(|(_, data)| !data.is_empty())
+                .map(|((time, _), data)| (time.as_i64(), data.iter().map(|s| s.0 .0).collect()))
+                .collect();
+        }
 
-            let range = re_data_store::RangeQuery::new(query.timeline, time_range);
+        // Blocks are always queried from the one in effect at the range start, as it may reach
+        // into the range even when extending a loaded range
+        let block_range = extend_to_previous_row(
+            ctx,
+            self.timeline,
+            entity_path,
+            range,
+            self.history_range,
+            [SampleBlock::name()],
+        );
+        let block_points_result = range_with_blueprint_resolved_data(
+            ctx,
+            None,
+            &re_data_store::RangeQuery::new(self.timeline, block_range),
+            series_result,
+            [SampleBlock::name()],
+        );
 
-            let scalar_points_result = range_with_blueprint_resolved_data(
-                ctx,
-                None,
-                &range,
-                &series_result,
-                [Scalar::name()],
+        if let Some(all_blocks) =
+            block_points_result.get_required_component_dense::<SampleBlock>(resolver)
+        {
+            let all_blocks = all_blocks?;
+
+            let entry_range = all_blocks.entry_range();
+
+            rows.pending |= !matches!(
+                all_blocks.status(),
+                (PromiseResult::Ready(()), PromiseResult::Ready(()))
             );
 
-            if let Some(all_scalars) =
-                scalar_points_result.get_required_component_dense::<Scalar>(resolver)
+            // Only the samples within the range are expanded, and with `extend_to_previous` the
+            // one holding at its start. Block `i` of a row goes to the channel `entity[i]`, at the
+            // times where the lower channels have a sample too, and later blocks replace the
+            // samples of earlier ones they overlap
+            let (min, max) = (range.min().as_i64(), range.max().as_i64());
+            let mut samples: BTreeMap<WaveformTime, Vec<f64>> = BTreeMap::new();
+            for ((time, _), data) in all_blocks
+                .range_indices(entry_range.clone())
+                .zip(all_blocks.range_data(entry_range))
             {
-                let all_scalars = all_scalars?;
+                let start = time.as_i64();
+                for (instance, block) in data.iter().enumerate() {
+                    let indices = if extend_to_previous {
+                        block.indices_from_previous(start, min, max)
+                    } else {
+                        block.indices_within(start, min, max)
+                    };
+                    for index in indices {
+                        let time = start.saturating_add(block.sample_offset(index));
+                        let values = samples.entry(time).or_default();
+                        let value = block.values[index];
+                        if instance < values.len() {
+                            values[instance] = value;
+                        } else if instance == values.len() {
+                            values.push(value);
+                        }
+                    }
+                }
+            }
+            rows.scalars
+                .extend(samples.into_iter().filter(|(_, values)| !values.is_empty()));
+        }
+
+        // Calibration in effect at each scalar row, so scalars are loaded in physical units. Each
+        // component is queried from its own row in effect at the earliest scalar on, however long
+        // before it that was logged, as they are logged independently of the scalars and of each
+        // other. Unit and interpolation are the latest ones
+        let calibration_range = ResolvedTimeRange::new(
+            rows.scalars
+                .iter()
+                .map(|(time, _)| TimeInt::new_temporal(*time))
+                .min()
+                .map_or(range.min(), |first| first.min(range.min())),
+            range.max(),
+        );
+        let calibration_result = |component_name: ComponentName| {
+            let range = extend_to_previous_row(
+                ctx,
+                self.timeline,
+                entity_path,
+                calibration_range,
+                ResolvedTimeRange::EVERYTHING,
+                [component_name],
+            );
+            range_with_blueprint_resolved_data(
+                ctx,
+                None,
+                &re_data_store::RangeQuery::new(self.timeline, range),
+                series_result,
+                [component_name],
+            )
+        };
+
+        let scales = component_rows::<Scale>(
+            &calibration_result(Scale::name()),
+            resolver,
+            &mut rows.pending,
+        )?;
+        let offsets = component_rows::<Offset>(
+            &calibration_result(Offset::name()),
+            resolver,
+            &mut rows.pending,
+        )?;
+        rows.unit = latest_component::<Unit>(
+            &calibration_result(Unit::name()),
+            resolver,
+            &mut rows.pending,
+        )?
+        .map(|unit| unit.0.to_string());
+        rows.interpolation = latest_component::<Interpolation>(
+            &calibration_result(Interpolation::name()),
+            resolver,
+            &mut rows.pending,
+        )?;
+
+        if !scales.is_empty() || !offsets.is_empty() {
+            for (time, values) in &mut rows.scalars {
+                let scale = scales.range(..=*time).next_back().map_or(1.0, |(_, s)| s.0);
+                let offset = offsets
+                    .range(..=*time)
+                    .next_back()
+                    .map_or(0.0, |(_, o)| o.0);
+                if scale != 1.0 || offset != 0.0 {
+                    for value in values {
+                        *value = *value * scale + offset;
+                    }
+                }
+            }
+        }
 
-                let entry_range = all_scalars.entry_range();
+        let color_result = range_with_blueprint_resolved_data(
+            ctx,
+            None,
+            &range_query(re_types::components::Color::name()),
+            series_result,
+            [re_types::components::Color::name()],
+        );
 
-                if !matches!(
-                    all_scalars.status(),
-                    (PromiseResult::Ready(()), PromiseResult::Ready(()))
-                ) {}
+        if let Some(all_colors) =
+            color_result.get_required_component_dense::<re_types::components::Color>(resolver)
+        {
+            let all_colors = all_colors?;
 
-                series.analog_points.points = all_scalars
-                    .range_indices(entry_range.clone())
-                    .zip(all_scalars.range_data(entry_range))
-                    .filter_map(|((time, _), data)| {
-                        if data.len() > 1 {
-                            return None;
-                        } else if data.is_empty() {
-                            return None;
-                        } else {
-                            let time = time.as_i64();
-                            min_time = min_time.map(|t: i64| t.min(time)).or(Some(time));
-                            max_time = max_time.map(|t: i64| t.max(time)).or(Some(time));
-
-                            let value = data.first().map_or(0.0, |s| s.0 .0);
-
-                            if let Some((y_min_prev, y_max_prev)) = series.analog_points.y_range {
-                                series.analog_points.y_range =
-                                    Some((y_min_prev.min(value), y_max_prev.max(value)));
-                            } else {
-                                series.analog_points.y_range = Some((value, value));
-                            }
-
-                            return Some((time, AnalogPoint { value }));
-                        }
+            let entry_range = all_colors.entry_range();
+
+            rows.pending |= !matches!(
+                all_colors.status(),
+                (PromiseResult::Ready(()), PromiseResult::Ready(()))
+            );
+
+            // The latest color holds for the whole range
+            if let Some(data) = all_colors
+                .range_data(entry_range)
+                .filter(|data| !data.is_empty())
+                .last()
+            {
+                rows.colors = data
+                    .iter()
+                    .map(|color| {
+                        let [r, g, b, a] = color.0.to_array();
+                        egui::Color32::from_rgba_unmultiplied(r, g, b, a)
                     })
                     .collect();
             }
+        }
 
-            let discrete_points_result = range_with_blueprint_resolved_data(
-                ctx,
-                None,
-                &range,
-                &series_result,
-                [
-                    DiscreteState::name(),
-                    DiscreteStateInit::name(),
-                    DiscreteStateNormal::name(),
-                ],
+        let discrete_points_result = range_with_blueprint_resolved_data(
+            ctx,
+            None,
+            &range_query(DiscreteState::name()),
+            series_result,
+            [DiscreteState::name(), DiscreteStateInit::name()],
+        );
+
+        // The normal state is queried over the range and latest-at its start, so that it is found
+        // however far before the range it was logged, also when extending a loaded range
+        let normal_range = extend_to_previous_row(
+            ctx,
+            self.timeline,
+            entity_path,
+            range,
+            self.history_range,
+            [DiscreteStateNormal::name()],
+        );
+        let discrete_normal_result = range_with_blueprint_resolved_data(
+            ctx,
+            None,
+            &re_data_store::RangeQuery::new(self.timeline, normal_range),
+            series_result,
+            [DiscreteStateNormal::name()],
+        );
+
+        if let (Some(all_discretes), Some(all_discrete_init), Some(all_discrete_normal)) = (
+            discrete_points_result.get_required_component_dense::<DiscreteState>(resolver),
+            discrete_points_result.get_required_component_dense::<DiscreteStateInit>(resolver),
+            discrete_normal_result.get_required_component_dense::<DiscreteStateNormal>(resolver),
+        ) {
+            let (all_discretes, all_discretes_init, all_discrete_normal) =
+                (all_discretes?, all_discrete_init?, all_discrete_normal?);
+
+            let (entry_range, discrete_init_entry_range, discrete_normal_entry_range) = (
+                all_discretes.entry_range(),
+                all_discretes_init.entry_range(),
+                all_discrete_normal.entry_range(),
             );
 
-            if let (Some(all_discretes), Some(all_discrete_init), Some(all_discrete_normal)) = (
-                discrete_points_result.get_required_component_dense::<DiscreteState>(resolver),
-                discrete_points_result.get_required_component_dense::<DiscreteStateInit>(resolver),
-                discrete_points_result
-                    .get_required_component_dense::<DiscreteStateNormal>(resolver),
-            ) {
-                let (all_discretes, all_discretes_init, all_discrete_normal) =
-                    (all_discretes?, all_discrete_init?, all_discrete_normal?);
-
-                let (entry_range, discrete_init_entry_range, discrete_normal_entry_range) = (
-                    all_discretes.entry_range(),
-                    all_discretes_init.entry_range(),
-                    all_discrete_normal.entry_range(),
-                );
+            rows.pending |= !matches!(
+                all_discretes.status(),
+                (PromiseResult::Ready(()), PromiseResult::Ready(()))
+            );
 
-                if !matches!(
-                    all_discretes.status(),
-                    (PromiseResult::Ready(()), PromiseResult::Ready(()))
-                ) {}
-
-                if !matches!(
-                    all_discretes_init.status(),
-                    (PromiseResult::Ready(()), PromiseResult::Ready(()))
-                ) {}
-
-                if !matches!(
-                    all_discrete_normal.status(),
-                    (PromiseResult::Ready(()), PromiseResult::Ready(()))
-                ) {}
-
-                let all_discretes_normal: Vec<ClassId> = all_discrete_normal
-                    .range_data(discrete_normal_entry_range)
-                    .filter_map(|data| {
-                        if data.len() > 1 {
-                            return None;
-                        } else if data.is_empty() {
-                            return None;
-                        }
+            rows.pending |= !matches!(
+                all_discretes_init.status(),
+                (PromiseResult::Ready(()), PromiseResult::Ready(()))
+            );
 
-                        let value: Option<&DiscreteStateNormal> = data.first();
-                        value.map(|d| d.0.clone())
-                    })
+            rows.pending |= !matches!(
+                all_discrete_normal.status(),
+                (PromiseResult::Ready(()), PromiseResult::Ready(()))
+            );
+
+            // Normal state per instance, a single instance applies to all of them
+            let discrete_normal: Vec<ClassId> = all_discrete_normal
+                .range_data(discrete_normal_entry_range)
+                .filter(|data| !data.is_empty())
+                .last()
+                .map(|data| data.iter().map(|d| d.0).collect())
+                .unwrap_or_default();
+
+            let transition = |instance: usize, class_id: ClassId| {
+                let annotation_info = annotation_map
+                    .get_annotation(entity_path, Some(class_id))
+                    .annotation_info
+                    .ok_or(RowIssue::UnknownClassId(class_id))?;
+
+                let normal = discrete_normal
+                    .get(instance)
+                    .or_else(|| (discrete_normal.len() == 1).then(|| &discrete_normal[0]));
+
+                let kind = if Some(&class_id) == normal {
+                    DiscreteTransitionKind::Line
+                } else {
+                    DiscreteTransitionKind::Box
+                };
+
+                Ok(DiscreteTransition {
+                    label: annotation_info.label.clone().map(Utf8::into),
+                    color: annotation_info_color(&annotation_info),
+                    kind,
+                })
+            };
+
+            rows.discretes = all_discretes
+                .range_indices(entry_range.clone())
+                .zip(all_discretes.range_data(entry_range))
+                .filter(|(_, data)| !data.is_empty())
+                .map(|((time, _), data)| {
+                    let transitions = data
+                        .iter()
+                        .enumerate()
+                        .map(|(instance, DiscreteState(class_id))| transition(instance, *class_id))
+                        .collect();
+                    (time.as_i64(), transitions)
+                })
+                .collect();
+
+            if let Some(data) = all_discretes_init
+                .range_data(discrete_init_entry_range)
+                .find(|data| !data.is_empty())
+            {
+                rows.init = data
+                    .iter()
+                    .enumerate()
+                    .map(|(instance, DiscreteStateInit(class_id))| transition(instance, *class_id))
                     .collect();
+            }
+        }
 
-                let discrete_normal = all_discretes_normal.get(0).cloned();
+        let bus_points_result = range_with_blueprint_resolved_data(
+            ctx,
+            None,
+            &range_query(BusValue::name()),
+            series_result,
+            [BusValue::name()],
+        );
 
-                series.discrete_points.transitions = all_discretes
-                    .range_indices(entry_range.clone())
-                    .zip(all_discretes.range_data(entry_range))
-                    .filter_map(|((time, _), data)| {
-                        //Only one discrete point allowed for entity path, ignore if otherwise
-                        if data.len() > 1 || data.is_empty() {
-                            return None;
-                        }
+        if let Some(all_buses) =
+            bus_points_result.get_required_component_dense::<BusValue>(resolver)
+        {
+            let all_buses = all_buses?;
 
-                        let value: Option<DiscreteState> = data.first().cloned();
-                        Some((time.clone(), value))
-                    })
-                    .filter_map(|(time, value)| {
-                        let time = time.as_i64();
-                        min_time = min_time.map(|t: i64| t.min(time)).or(Some(time));
-                        max_time = max_time.map(|t: i64| t.max(time)).or(Some(time));
-
-                        if let Some(DiscreteState(class_id)) = value {
-                            if let Some(annotation_info) = annotation_map
-                                .get_annotation(&entity_path.clone(), Some(class_id))
-                                .annotation_info
-                            {
-                                let label = annotation_info.label.clone().map(Utf8::into);
-                                let kind = if Some(class_id) == discrete_normal {
-                                    DiscreteTransitionKind::Line
-                                } else {
-                                    DiscreteTransitionKind::Box
-                                };
-
-                                return Some((
-                                    time,
-                                    DiscreteTransition {
-                                        label,
-                                        color: annotation_info_color(&annotation_info),
-                                        kind,
+            let entry_range = all_buses.entry_range();
+
+            rows.pending |= !matches!(
+                all_buses.status(),
+                (PromiseResult::Ready(()), PromiseResult::Ready(()))
+            );
+
+            // Buses are drawn in their channel's color, the value is the label
+            rows.discretes.extend(
+                all_buses
+                    .range_indices(entry_range.clone())
+                    .zip(all_buses.range_data(entry_range))
+                    .filter(|(_, data)| !data.is_empty())
+                    .map(|((time, _), data)| {
+                        let transitions = data
+                            .iter()
+                            .enumerate()
+                            .map(|(instance, bus)| {
+                                Ok(DiscreteTransition {
+                                    label: None,
+                                    color: channel_color(entity_path, instance),
+                                    kind: DiscreteTransitionKind::Bus {
+                                        value: bus.value,
+                                        width: bus.width,
                                     },
-                                ));
-                            }
-                        }
-                        return None;
-                    })
-                    .collect();
+                                })
+                            })
+                            .collect();
+                        (time.as_i64(), transitions)
+                    }),
+            );
+        }
 
-                let discrete_init_data: Option<_> = all_discretes_init
-                    .range_data(discrete_init_entry_range)
-                    .find_map(|(data)| {
-                        if data.len() > 1 || data.is_empty() {
-                            return None;
-                        }
+        let text_points_result = range_with_blueprint_resolved_data(
+            ctx,
+            None,
+            &range_query(TextState::name()),
+            series_result,
+            [TextState::name()],
+        );
 
-                        data.first().cloned()
-                    });
+        if let Some(all_texts) =
+            text_points_result.get_required_component_dense::<TextState>(resolver)
+        {
+            let all_texts = all_texts?;
 
-                if let Some(DiscreteStateInit(class_id)) = discrete_init_data {
-                    if let Some(annotation_info) = annotation_map
-                        .get_annotation(&entity_path.clone(), Some(class_id))
-                        .annotation_info
-                    {
-                        let kind = if Some(class_id) == discrete_normal {
-                            DiscreteTransitionKind::Line
-                        } else {
-                            DiscreteTransitionKind::Box
-                        };
-                        series.discrete_points.init = Some(DiscreteTransition {
-                            label: annotation_info.label.clone().map(Utf8::into),
-                            color: annotation_info_color(&annotation_info),
-                            kind,
-                        });
-                    }
-                }
-            }
+            let entry_range = all_texts.entry_range();
 
-            let event_points_result = range_with_blueprint_resolved_data(
-                ctx,
-                None,
-                &range,
-                &series_result,
-                [Event::name()],
+            rows.pending |= !matches!(
+                all_texts.status(),
+                (PromiseResult::Ready(()), PromiseResult::Ready(()))
             );
 
-            if let Some(all_events) =
-                event_points_result.get_required_component_dense::<Event>(resolver)
-            {
-                let all_events = all_events?;
+            rows.discretes.extend(
+                all_texts
+                    .range_indices(entry_range.clone())
+                    .zip(all_texts.range_data(entry_range))
+                    .filter(|(_, data)| !data.is_empty())
+                    .map(|((time, _), data)| {
+                        let transitions = data
+                            .iter()
+                            .map(|TextState(text)| {
+                                Ok(DiscreteTransition {
+                                    label: Some(text.to_string()),
+                                    color: text_state_color(text.as_str()),
+                                    kind: DiscreteTransitionKind::Box,
+                                })
+                            })
+                            .collect();
+                        (time.as_i64(), transitions)
+                    }),
+            );
+        }
 
-                let entry_range = all_events.entry_range();
+        let logic_points_result = range_with_blueprint_resolved_data(
+            ctx,
+            None,
+            &range_query(LogicBit::name()),
+            series_result,
+            [LogicBit::name()],
+        );
 
-                if !matches!(
-                    all_events.status(),
-                    (PromiseResult::Ready(()), PromiseResult::Ready(()))
-                ) {}
+        if let Some(all_bits) =
+            logic_points_result.get_required_component_dense::<LogicBit>(resolver)
+        {
+            let all_bits = all_bits?;
+
+            let entry_range = all_bits.entry_range();
+
+            rows.pending |= !matches!(
+                all_bits.status(),
+                (PromiseResult::Ready(()), PromiseResult::Ready(()))
+            );
 
-                all_events
+            rows.discretes.extend(
+                all_bits
                     .range_indices(entry_range.clone())
-                    .zip(all_events.range_data(entry_range))
-                    .for_each(|((time, _), data)| {
-                        if !data.is_empty() {
-                            let time = time.as_i64();
-                            min_time = min_time.map(|t: i64| t.min(time)).or(Some(time));
-                            max_time = max_time.map(|t: i64| t.max(time)).or(Some(time));
-
-                            for Event(class_id) in data.iter() {
-                                if let Some(annotation_info) = annotation_map
-                                    .get_annotation(&entity_path.clone(), Some(*class_id))
-                                    .annotation_info
-                                {
-                                    self.all_events.push(
-                                        time,
-                                        EventMarker {
-                                            entity_path: entity_path.clone(),
-                                            label: annotation_info.label.clone().map(Utf8::into),
-                                            color: annotation_info_color(&annotation_info),
-                                        },
-                                    );
-                                }
-                            }
-                        }
-                    });
-            }
+                    .zip(all_bits.range_data(entry_range))
+                    .filter(|(_, data)| !data.is_empty())
+                    .map(|((time, _), data)| {
+                        let transitions = data
+                            .iter()
+                            .enumerate()
+                            .map(|(instance, LogicBit(state))| {
+                                Ok(DiscreteTransition {
+                                    label: Some(state.as_str().to_owned()),
+                                    color: logic_state_color(*state)
+                                        .unwrap_or_else(|| channel_color(entity_path, instance)),
+                                    kind: DiscreteTransitionKind::Logic(*state),
+                                })
+                            })
+                            .collect();
+                        (time.as_i64(), transitions)
+                    }),
+            );
+        }
 
-            if min_time.is_none() && max_time.is_none() {
-                //Empty series
-                return Ok(());
-            } else if min_time.is_none() || max_time.is_none() {
-                //Partial series
-                let time = min_time.or(max_time);
+        let event_points_result = range_with_blueprint_resolved_data(
+            ctx,
+            None,
+            &re_data_store::RangeQuery::new(self.timeline, range),
+            series_result,
+            [Event::name()],
+        );
 
-                min_time = time;
-                max_time = time;
-            }
+        if let Some(all_events) =
+            event_points_result.get_required_component_dense::<Event>(resolver)
+        {
+            let all_events = all_events?;
+
+            let entry_range = all_events.entry_range();
 
-            series.min_time = min_time.unwrap();
-            series.max_time = max_time.unwrap();
+            rows.pending |= !matches!(
+                all_events.status(),
+                (PromiseResult::Ready(()), PromiseResult::Ready(()))
+            );
+
+            rows.events = all_events
+                .range_indices(entry_range.clone())
+                .zip(all_events.range_data(entry_range))
+                .filter(|(_, data)| !data.is_empty())
+                .map(|((time, _), data)| {
+                    let event_markers = data
+                        .iter()
+                        .map(|Event(class_id)| {
+                            let annotation_info = annotation_map
+                                .get_annotation(entity_path, Some(*class_id))
+                                .annotation_info
+                                .ok_or(RowIssue::UnknownClassId(*class_id))?;
 
-            self.all_series
-                .entry(domain.clone())
-                .and_modify(|v| {
-                    v.push(series.clone());
+                            Ok(EventMarker {
+                                entity_path: entity_path.clone(),
+                                label: annotation_info.label.clone().map(Utf8::into),
+                                color: annotation_info_color(&annotation_info),
+                                duration: None,
+                            })
+                        })
+                        .collect();
+                    (time.as_i64(), event_markers)
                 })
-                .or_insert(vec![series.clone()]);
+                .collect();
+        }
 
-            Ok(())
-        })?;
+        // Any span starting before the range may still be running within it, however long ago it
+        // started, so spans are queried from the start of the history and those that ended before
+        // the range are dropped
+        let (span_range, spans_end_from) = if extend_to_previous {
+            (
+                ResolvedTimeRange::new(self.history_range.min().min(range.min()), range.max()),
+                range.min().as_i64(),
+            )
+        } else {
+            (range, i64::MIN)
+        };
+        let span_points_result = range_with_blueprint_resolved_data(
+            ctx,
+            None,
+            &re_data_store::RangeQuery::new(self.timeline, span_range),
+            series_result,
+            [EventSpan::name()],
+        );
 
-        Ok(())
+        if let Some(all_spans) =
+            span_points_result.get_required_component_dense::<EventSpan>(resolver)
+        {
+            let all_spans = all_spans?;
+
+            let entry_range = all_spans.entry_range();
+
+            rows.pending |= !matches!(
+                all_spans.status(),
+                (PromiseResult::Ready(()), PromiseResult::Ready(()))
+            );
+
+            // Merged with the instant events of the same rows, as rows are stored by time
+            let mut events: BTreeMap<_, Vec<_>> =
+                std::mem::take(&mut rows.events).into_iter().collect();
+
+            for ((time, _), data) in all_spans
+                .range_indices(entry_range.clone())
+                .zip(all_spans.range_data(entry_range))
+                .filter(|(_, data)| !data.is_empty())
+            {
+                let start = time.as_i64();
+                if !data
+                    .iter()
+                    .any(|span| start.saturating_add(span.duration.max(0)) >= spans_end_from)
+                {
+                    continue;
+                }
+
+                let span_markers = data.iter().map(|span| {
+                    let annotation_info = annotation_map
+                        .get_annotation(entity_path, Some(span.class_id))
+                        .annotation_info
+                        .ok_or(RowIssue::UnknownClassId(span.class_id))?;
+
+                    Ok(EventMarker {
+                        entity_path: entity_path.clone(),
+                        label: annotation_info.label.clone().map(Utf8::into),
+                        color: annotation_info_color(&annotation_info),
+                        duration: Some(span.duration.max(0)),
+                    })
+                });
+                events
+                    .entry(time.as_i64())
+                    .or_default()
+                    .extend(span_markers);
+            }
+
+            rows.events = events.into_iter().collect();
+        }
+
+        Ok(rows)
+    }
+}
+
+/// Rows of one entity as queried, holding one value per instance.
+///
+/// Rows with several instances are split into sub-channels: instance `i` of every row belongs to
+/// the channel `entity[i]`. Values that can't be drawn are kept as the [`RowIssue`] explaining
+/// why, so they can be reported.
+#[derive(Default)]
+pub struct LoadedRows {
+    pub scalars: Vec<(WaveformTime, Vec<f64>)>,
+
+    /// Transitions per instance
+    pub discretes: Vec<(WaveformTime, Vec<Result<DiscreteTransition, RowIssue>>)>,
+
+    /// Init state per instance, a single instance applies to all channels
+    pub init: Vec<Result<DiscreteTransition, RowIssue>>,
+
+    /// Color per instance, a single color applies to all channels
+    pub colors: Vec<egui::Color32>,
+
+    /// Unit of the scalars, which are already calibrated
+    pub unit: Option<String>,
+
+    pub interpolation: Option<Interpolation>,
+
+    pub events: Vec<(WaveformTime, Vec<Result<EventMarker, RowIssue>>)>,
+
+    /// Some of the queried data isn't available yet
+    pub pending: bool,
+}
+
+impl LoadedRows {
+    /// Number of channels the rows are split into
+    pub fn channel_count(&self) -> usize {
+        let scalars = self.scalars.iter().map(|(_, values)| values.len());
+        let discretes = self
+            .discretes
+            .iter()
+            .map(|(_, transitions)| transitions.len());
+
+        scalars
+            .chain(discretes)
+            .chain([self.init.len()])
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Resolves the entity's visible history setting relative to the time cursor
+fn visible_history_range(
+    query_range: &re_viewer_context::QueryRange,
+    time_cursor: TimeInt,
+) -> ResolvedTimeRange {
+    let visible_history = match query_range {
+        re_viewer_context::QueryRange::TimeRange(range) => range.clone(),
+        // A single instant can't be drawn as a waveform, fall back to the whole history
+        re_viewer_context::QueryRange::LatestAt => TimeRange::EVERYTHING,
+    };
+
+    ResolvedTimeRange::from_relative_time_range(&visible_history, time_cursor)
+}
+
+/// Limits `history_range` to the visible window plus a margin on either side
+fn clamp_to_window(
+    history_range: ResolvedTimeRange,
+    visible_window: Option<(WaveformTime, WaveformTime)>,
+) -> ResolvedTimeRange {
+    let mut range = history_range;
+
+    if let Some((window_min, window_max)) = visible_window {
+        let margin = ((window_max - window_min) as f64 * QUERY_WINDOW_MARGIN_PC) as i64;
+
+        range.set_min(TimeInt::new_temporal(
+            range.min().as_i64().max(window_min.saturating_sub(margin)),
+        ));
+        range.set_max(TimeInt::new_temporal(
+            range.max().as_i64().min(window_max.saturating_add(margin)),
+        ));
+    }
+
+    range
+}
+
+/// Extends `window_range` back to the last row holding any of `component_names` at or before its
+/// start, without leaving the visible history
+fn extend_to_previous_row(
+    ctx: &re_viewer_context::ViewContext<'_>,
+    timeline: Timeline,
+    entity_path: &EntityPath,
+    window_range: ResolvedTimeRange,
+    history_range: ResolvedTimeRange,
+    component_names: impl IntoIterator<Item = ComponentName>,
+) -> ResolvedTimeRange {
+    let mut range = window_range;
+
+    let results = ctx.recording().query_caches().latest_at(
+        ctx.recording().store(),
+        &LatestAtQuery::new(timeline, window_range.min()),
+        entity_path,
+        component_names,
+    );
+
+    if let Some(previous) = results
+        .components
+        .values()
+        .map(|component| component.index().0)
+        .filter(|time| !time.is_static())
+        .max()
+    {
+        range.set_min(previous.max(history_range.min()).min(window_range.min()));
+    }
+
+    range
+}
+
+/// Value of component `C` in each row of `results` holding one, by time
+fn component_rows<C: re_types::Component>(
+    results: &re_space_view::HybridRangeResults,
+    resolver: &re_query::PromiseResolver,
+    pending: &mut bool,
+) -> Result<BTreeMap<WaveformTime, C>, QueryError> {
+    let Some(all_values) = results.get_required_component_dense::<C>(resolver) else {
+        return Ok(BTreeMap::new());
+    };
+    let all_values = all_values?;
+
+    *pending |= !matches!(
+        all_values.status(),
+        (PromiseResult::Ready(()), PromiseResult::Ready(()))
+    );
+
+    let entry_range = all_values.entry_range();
+    Ok(all_values
+        .range_indices(entry_range.clone())
+        .zip(all_values.range_data(entry_range))
+        .filter_map(|((time, _), data)| Some((time.as_i64(), data.first()?.clone())))
+        .collect())
+}
+
+/// Last value of component `C` in `results`
+fn latest_component<C: re_types::Component>(
+    results: &re_space_view::HybridRangeResults,
+    resolver: &re_query::PromiseResolver,
+    pending: &mut bool,
+) -> Result<Option<C>, QueryError> {
+    let Some(all_values) = results.get_required_component_dense::<C>(resolver) else {
+        return Ok(None);
+    };
+    let all_values = all_values?;
+
+    *pending |= !matches!(
+        all_values.status(),
+        (PromiseResult::Ready(()), PromiseResult::Ready(()))
+    );
+
+    let entry_range = all_values.entry_range();
+    Ok(all_values
+        .range_data(entry_range)
+        .filter_map(|data| data.first().cloned())
+        .last())
+}
+
+/// Color of a text state, the same text always gets the same color.
+///
+/// The text is hashed with FNV-1a, which unlike the standard library's hasher is stable across
+/// Rust releases, so colors don't change between viewer builds
+fn text_state_color(text: &str) -> egui::Color32 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+    let hash = text.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+    auto_color_egui(hash as u16)
+}
+
+/// Fixed color of the `X` and `Z` logic states, 0 and 1 use the channel's color
+fn logic_state_color(state: LogicState) -> Option<egui::Color32> {
+    match state {
+        LogicState::Unknown => Some(egui::Color32::RED),
+        LogicState::HighImpedance => Some(egui::Color32::GOLD),
+        LogicState::Low | LogicState::High => None,
     }
 }
 